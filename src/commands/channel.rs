@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use pumpkin::command::{
+    args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
+    dispatcher::CommandError,
+    dispatcher::CommandError::{InvalidConsumption, InvalidRequirement},
+    tree::CommandTree,
+    tree::builder::{argument, literal, require},
+    CommandExecutor, CommandSender,
+};
+use pumpkin_util::text::TextComponent;
+use crate::storage::{PLAYER_CHANNELS, PLAYER_FOCUSED_CHANNEL, PLUGIN_CONFIG};
+use crate::utils::{can_use_channel, get_channel_config, save_data, GLOBAL_CHANNEL};
+
+const NAMES: [&str; 2] = ["ch", "channel"];
+const DESCRIPTION: &str = "Join, leave or focus a chat channel.";
+const ARG_CHANNEL: &str = "channel";
+
+#[derive(Clone, Copy)]
+enum ChannelAction {
+    Join,
+    Leave,
+    Focus,
+}
+
+pub struct ChannelExecutor(ChannelAction);
+
+#[async_trait]
+impl CommandExecutor for ChannelExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+        let Some(Arg::Simple(channel_name)) = args.get(ARG_CHANNEL) else {
+            return Err(InvalidConsumption(Some(ARG_CHANNEL.into())));
+        };
+        let channel_name = channel_name.to_lowercase();
+        let uuid = p.gameprofile.id;
+
+        // Retour au chat global
+        if channel_name == GLOBAL_CHANNEL {
+            if matches!(self.0, ChannelAction::Focus) {
+                PLAYER_FOCUSED_CHANNEL.lock().await.remove(&uuid);
+                p.send_system_message(&TextComponent::text("You are now talking in global chat.")).await;
+                if let Err(e) = save_data().await {
+                    log::error!("[ChatColor] Failed to save player data: {}", e);
+                }
+            } else {
+                p.send_system_message(&TextComponent::text("Global chat can't be joined or left.")).await;
+            }
+            return Ok(());
+        }
+
+        let Some(channel) = get_channel_config(&channel_name).await else {
+            let available = {
+                let config = PLUGIN_CONFIG.lock().await;
+                config.channels.keys().cloned().collect::<Vec<_>>().join(", ")
+            };
+            p.send_system_message(&TextComponent::text(
+                format!("Unknown channel '{}'. Available: {}", channel_name, available)
+            )).await;
+            return Ok(());
+        };
+
+        let feedback = match self.0 {
+            ChannelAction::Join | ChannelAction::Focus => {
+                if !can_use_channel(&p, &channel).await {
+                    p.send_system_message(&TextComponent::text(
+                        format!("You don't have permission to use channel '{}'.", channel_name)
+                    )).await;
+                    return Ok(());
+                }
+                PLAYER_CHANNELS.lock().await.entry(uuid).or_default().insert(channel_name.clone());
+                if matches!(self.0, ChannelAction::Focus) {
+                    PLAYER_FOCUSED_CHANNEL.lock().await.insert(uuid, channel_name.clone());
+                    format!("You are now talking in channel '{}'. Use /ch focus global to go back.", channel_name)
+                } else {
+                    format!("You joined channel '{}'.", channel_name)
+                }
+            }
+            ChannelAction::Leave => {
+                {
+                    let mut channels = PLAYER_CHANNELS.lock().await;
+                    if let Some(joined) = channels.get_mut(&uuid) {
+                        joined.remove(&channel_name);
+                        if joined.is_empty() {
+                            channels.remove(&uuid);
+                        }
+                    }
+                }
+                // Quitter le canal actif ramène au chat global
+                let mut focused = PLAYER_FOCUSED_CHANNEL.lock().await;
+                if focused.get(&uuid) == Some(&channel_name) {
+                    focused.remove(&uuid);
+                }
+                format!("You left channel '{}'.", channel_name)
+            }
+        };
+
+        p.send_system_message(&TextComponent::text(feedback)).await;
+
+        if let Err(e) = save_data().await {
+            log::error!("[ChatColor] Failed to save player data: {}", e);
+        }
+        Ok(())
+    }
+}
+
+pub struct ChannelListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ChannelListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+
+        let channels: Vec<_> = {
+            let config = PLUGIN_CONFIG.lock().await;
+            let mut channels: Vec<_> = config.channels.iter().map(|(n, c)| (n.clone(), c.clone())).collect();
+            channels.sort_by(|a, b| a.0.cmp(&b.0));
+            channels
+        };
+        let joined = PLAYER_CHANNELS.lock().await.get(&p.gameprofile.id).cloned().unwrap_or_default();
+        let focused = PLAYER_FOCUSED_CHANNEL.lock().await.get(&p.gameprofile.id).cloned();
+
+        let mut lines = vec![format!(
+            "Channels (talking in: {}):",
+            focused.as_deref().unwrap_or(GLOBAL_CHANNEL)
+        )];
+        for (name, channel) in channels {
+            if !can_use_channel(&p, &channel).await {
+                continue;
+            }
+            let status = if joined.contains(&name) { "joined" } else { "not joined" };
+            lines.push(format!(" - {} ({})", name, status));
+        }
+        p.send_system_message(&TextComponent::text(lines.join("\n"))).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(ChannelListExecutor)
+            .then(literal("list").execute(ChannelListExecutor))
+            .then(literal("join").then(argument(ARG_CHANNEL, SimpleArgConsumer).execute(ChannelExecutor(ChannelAction::Join))))
+            .then(literal("leave").then(argument(ARG_CHANNEL, SimpleArgConsumer).execute(ChannelExecutor(ChannelAction::Leave))))
+            .then(literal("focus").then(argument(ARG_CHANNEL, SimpleArgConsumer).execute(ChannelExecutor(ChannelAction::Focus))))
+    )
+}
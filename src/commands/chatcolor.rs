@@ -1,16 +1,26 @@
 use async_trait::async_trait;
 use pumpkin::command::{
-    args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
+    args::{Arg, ArgumentConsumer, ConsumedArgs, GetClientSideArgParser, message::MessageArgConsumer, simple::SimpleArgConsumer},
     dispatcher::CommandError,
     dispatcher::CommandError::InvalidRequirement,
-    tree::CommandTree,
-    tree::builder::{argument, require},
+    tree::{CommandTree, RawArgs},
+    tree::builder::{argument, literal, require},
     CommandExecutor, CommandSender,
 };
+use pumpkin_protocol::java::client::play::{ArgumentType, CommandSuggestion, StringProto, SuggestionProviders};
 use pumpkin_util::permission::PermissionLvl;
 use pumpkin_util::text::color::NamedColor;
-use crate::{storage::{PLAYER_COLORS, PLUGIN_CONFIG}, config::ChatColorStyle};
-use crate::utils::save_data;
+use pumpkin_util::text::TextComponent;
+use crate::{storage::{HIDE_OTHER_COLORS, PLAYER_ACCESSIBILITY, PLAYER_COLORS, PLAYER_GRANTS, PLAYER_MESSAGE_FORMATS, PLAYER_NAME_COLORS, PLAYER_NAME_FORMATS, PLAYER_PRESET_PREFIXES, PLAYER_UNLOCKS, PLUGIN_CONFIG}, config::{AccessibilityMode, ChatColorStyle}};
+use crate::utils::{
+    apply_name_team, apply_style, build_color_menu, can_use_color, color_permission, display_name, entry_meta, grant_style,
+    is_entry_available, is_style_readable, open_color_gui, palette_names, parse_duration, parse_hex_color, preset_display_name,
+    preset_names, preset_permission, resolve_target, save_data, style_name, styled_player_message, styled_player_name, update_tab_name,
+    ColorTab, TargetPlayer,
+};
+
+/// Permission de /chatcolor custom (gradient personnel)
+pub const CUSTOM_GRADIENT_PERMISSION: &str = "chat-color:custom";
 
 // Fonction pour parser un code couleur Minecraft en NamedColor
 fn parse_color_code_section(code: &str) -> Option<NamedColor> {
@@ -44,9 +54,14 @@ pub async fn get_style_from_config(color_name: &str) -> Option<ChatColorStyle> {
     let config = PLUGIN_CONFIG.lock().await;
     let color_name = color_name.to_lowercase();
     
+    // Entrée saisonnière hors de sa période : pas sélectionnable
+    if !is_entry_available(&config, &color_name) {
+        return None;
+    }
+    
     // Vérifier d'abord les couleurs simples
-    if let Some(color_code) = config.simple_colors.get(&color_name) {
-        if let Some(named_color) = parse_color_code_section(color_code) {
+    if let Some(color) = config.simple_colors.get(&color_name) {
+        if let Some(named_color) = parse_color_code_section(color.code()) {
             return Some(ChatColorStyle::Simple(named_color));
         }
     }
@@ -60,22 +75,104 @@ pub async fn get_style_from_config(color_name: &str) -> Option<ChatColorStyle> {
 }
 
 // Fonction pour obtenir la liste des couleurs disponibles
-async fn get_available_colors() -> Vec<String> {
+pub async fn get_available_colors() -> Vec<String> {
     let config = PLUGIN_CONFIG.lock().await;
-    let mut colors = Vec::new();
     
-    // Ajouter les couleurs simples
-    colors.extend(config.simple_colors.keys().cloned());
-    
-    // Ajouter les gradients
-    colors.extend(config.gradients.keys().cloned());
+    // Couleurs simples et gradients, dans l'ordre de la palette (catégorie, ordre, nom)
+    let mut colors = palette_names(&config);
     
+    // Les entrées cachées et les entrées saisonnières hors de leur période n'apparaissent pas
+    colors.retain(|name| {
+        is_entry_available(&config, name) && !entry_meta(&config, name).is_some_and(|meta| meta.hidden)
+    });
     colors
 }
 
+// Résout un nom de couleur : styles spéciaux (pour la compatibilité) puis configuration
+pub async fn resolve_style(color_name: &str) -> Option<ChatColorStyle> {
+    match color_name.to_lowercase().as_str() {
+        "rainbow" => Some(ChatColorStyle::Rainbow),
+        "fire" => Some(ChatColorStyle::Fire),
+        other => get_style_from_config(other).await,
+    }
+}
+
+/// Liste des couleurs disponibles, chaque nom affiché dans son propre style
+pub async fn styled_color_list() -> TextComponent {
+    let mut list = TextComponent::text("");
+    for (i, name) in get_available_colors().await.iter().enumerate() {
+        if i > 0 {
+            list = list.add_child(TextComponent::text(", "));
+        }
+        let label = display_name(name).await;
+        let entry = match resolve_style(name).await {
+            Some(style) => apply_style(&label, &style).await,
+            None => TextComponent::text(label),
+        };
+        list = list.add_child(entry);
+    }
+    list
+}
+
 const NAMES: [&str; 1] = ["chatcolor"];
 const DESCRIPTION: &str = "Set your default chat color or gradient (ex: red, blue, rainbow, fire).";
 const ARG_COLOR: &str = "color";
+const ARG_TEXT: &str = "text";
+const ARG_PAGE: &str = "page";
+const ARG_TAB: &str = "tab";
+const ARG_STOPS: &str = "colors";
+const ARG_MODE: &str = "mode";
+const ARG_PLAYER: &str = "player";
+const ARG_DURATION: &str = "duration";
+const ARG_PRESET: &str = "preset";
+
+/// Argument couleur : un mot, complété avec les entrées de la palette (ordre de la palette,
+/// sans les entrées cachées ni hors saison) et leur nom affiché en info-bulle
+pub struct PaletteArgConsumer;
+
+impl GetClientSideArgParser for PaletteArgConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::String(StringProto::SingleWord)
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        Some(SuggestionProviders::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for PaletteArgConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a pumpkin::server::Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::Simple(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a pumpkin::server::Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        let input = input.to_lowercase();
+        let mut suggestions = Vec::new();
+        for name in get_available_colors().await {
+            if !name.starts_with(&input) {
+                continue;
+            }
+            let label = display_name(&name).await;
+            let tooltip = match resolve_style(&name).await {
+                Some(style) => apply_style(&label, &style).await,
+                None => TextComponent::text(label),
+            };
+            suggestions.push(CommandSuggestion::new(name, Some(tooltip)));
+        }
+        Ok(Some(suggestions))
+    }
+}
 
 pub struct ChatColorExecutor;
 
@@ -84,71 +181,683 @@ impl CommandExecutor for ChatColorExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender,
-        _server: &pumpkin::server::Server,
+        server: &pumpkin::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let Some(p) = sender.as_player() else {
             return Err(InvalidRequirement);
         };
         
-        // Seuls les OP peuvent utiliser la commande
-        if sender.permission_lvl() < PermissionLvl::One {
-            p.send_system_message(&pumpkin_util::text::TextComponent::text(
-                "You must be OP to use this command."
-            )).await;
-            return Ok(());
-        }
-
         let color_str = match args.get(ARG_COLOR) {
             Some(Arg::Simple(s)) => *s,
             _ => "",
         };
         
-        // Essayer d'abord les styles spéciaux (pour la compatibilité)
-        let style = if color_str.to_lowercase() == "rainbow" {
-            Some(ChatColorStyle::Rainbow)
-        } else if color_str.to_lowercase() == "fire" {
-            Some(ChatColorStyle::Fire)
-        } else {
-            // Sinon, chercher dans la configuration
-            get_style_from_config(&color_str.to_lowercase()).await
-        };
+        // Sans argument : menu cliquable de la palette
+        if color_str.is_empty() {
+            let current = PLAYER_COLORS.lock().await.get(&p.gameprofile.id).cloned();
+            p.send_system_message(&build_color_menu(&p, "chatcolor", "Chat colors", current.as_ref(), 1).await).await;
+            return Ok(());
+        }
+        
+        let style = resolve_style(color_str).await;
+        
+        if style.is_some() && !can_use_color(&p, color_str).await {
+            p.send_system_message(&TextComponent::text(format!(
+                "You don't have permission to use {} ({}).",
+                display_name(color_str).await, color_permission(color_str)
+            ))).await;
+            return Ok(());
+        }
+        
+        if let Some(style) = &style {
+            if !is_style_readable(style).await {
+                p.send_system_message(&TextComponent::text(
+                    "This color is too dark to be read in chat, pick a lighter one."
+                )).await;
+                return Ok(());
+            }
+        }
         
         if let Some(style) = style {
             {
                 let mut map = PLAYER_COLORS.lock().await;
                 map.insert(p.gameprofile.id, style.clone());
             }
-            let feedback = match &style {
-                ChatColorStyle::Simple(color) => format!("Your chat color is now set to {:?}", color),
-                ChatColorStyle::Rainbow => "Your chat color is now set to RAINBOW! 🌈".to_string(),
-                ChatColorStyle::Fire => "Your chat color is now set to FIRE! 🔥".to_string(),
-                ChatColorStyle::CustomGradient(gradient_name) => format!("Your chat color is now set to {} gradient!", gradient_name),
-            };
-            p.send_system_message(&pumpkin_util::text::TextComponent::text(feedback)).await;
+            // Une couleur choisie à la main remplace la mise en forme et le préfixe d'un preset
+            PLAYER_MESSAGE_FORMATS.lock().await.remove(&p.gameprofile.id);
+            if PLAYER_PRESET_PREFIXES.lock().await.remove(&p.gameprofile.id).is_some() {
+                update_tab_name(server, &p).await;
+            }
+            // Retour avec le nom affiché de l'entrée, dans son propre style
+            let label = display_name(color_str).await;
+            p.send_system_message(
+                &TextComponent::text("Your chat color is now set to ").add_child(apply_style(&label, &style).await)
+            ).await;
             
             // Sauvegarder les données après le changement
             if let Err(e) = save_data().await {
                 log::error!("[ChatColor] Failed to save player data: {}", e);
             }
         } else {
-            // Afficher la liste des couleurs disponibles
-            let available_colors = get_available_colors().await;
-            let color_list = available_colors.join(", ");
-            p.send_system_message(&pumpkin_util::text::TextComponent::text(
-                format!("Unknown color or style. Available: {}", color_list)
-            )).await;
+            // Afficher le menu des couleurs disponibles
+            let current = PLAYER_COLORS.lock().await.get(&p.gameprofile.id).cloned();
+            p.send_system_message(&TextComponent::text("Unknown color or style.")).await;
+            p.send_system_message(&build_color_menu(&p, "chatcolor", "Chat colors", current.as_ref(), 1).await).await;
         }
         
         Ok(())
     }
 }
 
+/// `/chatcolor menu <page>` : page du menu cliquable
+pub struct ChatColorMenuExecutor;
+
+#[async_trait]
+impl CommandExecutor for ChatColorMenuExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+        let page = match args.get(ARG_PAGE) {
+            Some(Arg::Simple(s)) => s.parse::<usize>().unwrap_or(1),
+            _ => 1,
+        };
+        let current = PLAYER_COLORS.lock().await.get(&p.gameprofile.id).cloned();
+        p.send_system_message(&build_color_menu(&p, "chatcolor", "Chat colors", current.as_ref(), page).await).await;
+        Ok(())
+    }
+}
+
+/// `/chatcolor gui [chat|name]` : sélecteur en coffre
+pub struct ChatColorGuiExecutor;
+
+#[async_trait]
+impl CommandExecutor for ChatColorGuiExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+        let tab = match args.get(ARG_TAB) {
+            Some(Arg::Simple(s)) if s.eq_ignore_ascii_case("name") => ColorTab::Name,
+            _ => ColorTab::Chat,
+        };
+        open_color_gui(&p, tab).await;
+        Ok(())
+    }
+}
+
+/// `/chatcolor custom <#hex> <#hex> [#hex...]` : gradient personnel à partir de couleurs hexadécimales
+pub struct ChatColorCustomExecutor;
+
+#[async_trait]
+impl CommandExecutor for ChatColorCustomExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+
+        if !p.has_permission(CUSTOM_GRADIENT_PERMISSION).await {
+            p.send_system_message(&TextComponent::text(format!(
+                "You don't have permission to create a custom gradient ({}).",
+                CUSTOM_GRADIENT_PERMISSION
+            ))).await;
+            return Ok(());
+        }
+
+        let Some(Arg::Msg(input)) = args.get(ARG_STOPS) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_STOPS.into())));
+        };
+
+        let mut stops = Vec::new();
+        for hex in input.split_whitespace() {
+            match parse_hex_color(hex) {
+                Some(rgb) => stops.push(rgb),
+                None => {
+                    p.send_system_message(&TextComponent::text(format!(
+                        "Invalid color {}, use hex colors like #ff8800.", hex
+                    ))).await;
+                    return Ok(());
+                }
+            }
+        }
+
+        let max_stops = PLUGIN_CONFIG.lock().await.settings.max_custom_stops;
+        if stops.len() < 2 || stops.len() > max_stops {
+            p.send_system_message(&TextComponent::text(format!(
+                "A custom gradient needs between 2 and {} colors.", max_stops
+            ))).await;
+            return Ok(());
+        }
+
+        let style = ChatColorStyle::PersonalGradient(stops);
+        if !is_style_readable(&style).await {
+            p.send_system_message(&TextComponent::text(
+                "Some of these colors are too dark to be read in chat, pick lighter ones."
+            )).await;
+            return Ok(());
+        }
+        PLAYER_COLORS.lock().await.insert(p.gameprofile.id, style.clone());
+        p.send_system_message(
+            &TextComponent::text("Your chat color is now set to ").add_child(apply_style("your custom gradient!", &style).await)
+        ).await;
+
+        // Sauvegarder les données après le changement
+        if let Err(e) = save_data().await {
+            log::error!("[ChatColor] Failed to save player data: {}", e);
+        }
+        Ok(())
+    }
+}
+
+/// `/chatcolor accessibility <mode|off>` : transformation des couleurs des messages reçus
+pub struct ChatColorAccessibilityExecutor;
+
+#[async_trait]
+impl CommandExecutor for ChatColorAccessibilityExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+        let Some(Arg::Simple(mode_str)) = args.get(ARG_MODE) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_MODE.into())));
+        };
+
+        if mode_str.eq_ignore_ascii_case("off") {
+            PLAYER_ACCESSIBILITY.lock().await.remove(&p.gameprofile.id);
+            p.send_system_message(&TextComponent::text("Accessibility mode disabled, you now see the normal colors.")).await;
+        } else if let Some(mode) = AccessibilityMode::from_name(mode_str) {
+            PLAYER_ACCESSIBILITY.lock().await.insert(p.gameprofile.id, mode);
+            p.send_system_message(&TextComponent::text(format!(
+                "Accessibility mode set to {}, colors in the messages you receive are adapted.", mode.as_str()
+            ))).await;
+        } else {
+            let modes: Vec<&str> = AccessibilityMode::ALL.iter().map(|mode| mode.as_str()).collect();
+            p.send_system_message(&TextComponent::text(format!(
+                "Unknown mode. Available: {}, off", modes.join(", ")
+            ))).await;
+            return Ok(());
+        }
+
+        // Sauvegarder les données après le changement
+        if let Err(e) = save_data().await {
+            log::error!("[ChatColor] Failed to save player data: {}", e);
+        }
+        Ok(())
+    }
+}
+
+/// `/chatcolor toggle others` : affiche ou masque les couleurs et tags des autres joueurs
+pub struct ChatColorToggleOthersExecutor;
+
+#[async_trait]
+impl CommandExecutor for ChatColorToggleOthersExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+
+        let hidden = {
+            let mut hide_other_colors = HIDE_OTHER_COLORS.lock().await;
+            if hide_other_colors.remove(&p.gameprofile.id) {
+                false
+            } else {
+                hide_other_colors.insert(p.gameprofile.id);
+                true
+            }
+        };
+        p.send_system_message(&TextComponent::text(if hidden {
+            "Other players' messages are now shown without colors."
+        } else {
+            "Other players' messages are shown with their colors again."
+        })).await;
+
+        // Sauvegarder les données après le changement
+        if let Err(e) = save_data().await {
+            log::error!("[ChatColor] Failed to save player data: {}", e);
+        }
+        Ok(())
+    }
+}
+
+/// `/chatcolor grant <player> <color> <duration>` : couleur de chat temporaire (OP niveau 2)
+pub struct ChatColorGrantExecutor;
+
+#[async_trait]
+impl CommandExecutor for ChatColorGrantExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        if sender.permission_lvl() < PermissionLvl::Two {
+            sender.send_message(TextComponent::text("You must be OP level 2 to grant colors.")).await;
+            return Ok(());
+        }
+        let Some(Arg::Simple(player_str)) = args.get(ARG_PLAYER) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_PLAYER.into())));
+        };
+        let Some(Arg::Simple(color_str)) = args.get(ARG_COLOR) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_COLOR.into())));
+        };
+        let Some(Arg::Simple(duration_str)) = args.get(ARG_DURATION) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_DURATION.into())));
+        };
+
+        let Some(style) = resolve_style(color_str).await else {
+            sender.send_message(
+                TextComponent::text("Unknown color or style. Available: ").add_child(styled_color_list().await)
+            ).await;
+            return Ok(());
+        };
+        let Some(duration) = parse_duration(duration_str) else {
+            sender.send_message(TextComponent::text(
+                "Invalid duration, use for example 30m, 12h, 7d or 1d12h."
+            )).await;
+            return Ok(());
+        };
+
+        // Pseudo ou UUID, le joueur peut être hors ligne (récompense de boutique...)
+        let Some(target) = resolve_target(server, player_str).await else {
+            sender.send_message(TextComponent::text(format!("Unknown player: {}", player_str))).await;
+            return Ok(());
+        };
+
+        let label = display_name(color_str).await;
+        grant_style(target.uuid, style.clone(), duration).await;
+        if let Some(player) = &target.online {
+            player.send_system_message(
+                &TextComponent::text("You received the chat color ")
+                    .add_child(apply_style(&label, &style).await)
+                    .add_child(TextComponent::text(format!(" for {}!", duration_str)))
+            ).await;
+        }
+        sender.send_message(TextComponent::text(format!(
+            "Granted {} to {} for {}", label, target.name, duration_str
+        ))).await;
+
+        if let Err(e) = save_data().await {
+            log::error!("[ChatColor] Failed to save player data: {}", e);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum UnlockAction {
+    Unlock,
+    Lock,
+}
+
+/// `/chatcolor unlock|lock <player> <color>` : débloque ou retire une entrée de la palette (OP niveau 2)
+pub struct ChatColorUnlockExecutor(UnlockAction);
+
+#[async_trait]
+impl CommandExecutor for ChatColorUnlockExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        if sender.permission_lvl() < PermissionLvl::Two {
+            sender.send_message(TextComponent::text("You must be OP level 2 to unlock or lock colors.")).await;
+            return Ok(());
+        }
+        let Some(Arg::Simple(player_str)) = args.get(ARG_PLAYER) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_PLAYER.into())));
+        };
+        let Some(Arg::Simple(color_str)) = args.get(ARG_COLOR) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_COLOR.into())));
+        };
+        let color_name = color_str.to_lowercase();
+
+        let Some(style) = resolve_style(&color_name).await else {
+            sender.send_message(
+                TextComponent::text("Unknown color or style. Available: ").add_child(styled_color_list().await)
+            ).await;
+            return Ok(());
+        };
+
+        // Pseudo ou UUID, le joueur peut être hors ligne
+        let Some(target) = resolve_target(server, player_str).await else {
+            sender.send_message(TextComponent::text(format!("Unknown player: {}", player_str))).await;
+            return Ok(());
+        };
+
+        {
+            let mut unlocks = PLAYER_UNLOCKS.lock().await;
+            if self.0 == UnlockAction::Unlock {
+                unlocks.entry(target.uuid).or_default().insert(color_name.clone());
+            } else if let Some(unlocked) = unlocks.get_mut(&target.uuid) {
+                unlocked.remove(&color_name);
+                if unlocked.is_empty() {
+                    unlocks.remove(&target.uuid);
+                }
+            }
+        }
+
+        let label = display_name(&color_name).await;
+        let feedback = match self.0 {
+            UnlockAction::Unlock => "Unlocked",
+            UnlockAction::Lock => "Locked",
+        };
+        sender.send_message(TextComponent::text(format!("{} {} for {}", feedback, label, target.name))).await;
+        match (self.0, &target.online) {
+            (UnlockAction::Unlock, Some(player)) => {
+                player.send_system_message(
+                    &TextComponent::text("You unlocked a new color: ").add_child(apply_style(&label, &style).await)
+                ).await;
+            }
+            (UnlockAction::Lock, _) => clear_locked_style(server, &target, &color_name).await,
+            (UnlockAction::Unlock, None) => {}
+        }
+
+        if let Err(e) = save_data().await {
+            log::error!("[ChatColor] Failed to save player data: {}", e);
+        }
+        Ok(())
+    }
+}
+
+/// Retire la couleur verrouillée du chat et du pseudo du joueur s'il ne peut plus l'utiliser
+/// (permission). Hors ligne, la permission est inconnue : la couleur est retirée et pourra être reprise.
+/// Une couleur temporaire en cours (/chatcolor grant) est conservée jusqu'à son expiration.
+async fn clear_locked_style(server: &pumpkin::server::Server, target: &TargetPlayer, color_name: &str) {
+    if let Some(player) = &target.online {
+        if can_use_color(player, color_name).await {
+            return;
+        }
+    }
+    let granted = PLAYER_GRANTS.lock().await.get(&target.uuid).map(|grant| grant.style.clone());
+
+    let chat_style = PLAYER_COLORS.lock().await.get(&target.uuid).cloned();
+    if let Some(chat_style) = chat_style {
+        if Some(&chat_style) != granted.as_ref() && style_name(&chat_style).await == color_name {
+            PLAYER_COLORS.lock().await.remove(&target.uuid);
+        }
+    }
+    let name_style = PLAYER_NAME_COLORS.lock().await.get(&target.uuid).cloned();
+    if let Some(name_style) = name_style {
+        if style_name(&name_style).await == color_name {
+            PLAYER_NAME_COLORS.lock().await.remove(&target.uuid);
+            // Nametag et liste des joueurs reprennent la couleur par défaut
+            if let Some(player) = &target.online {
+                apply_name_team(server, player).await;
+                update_tab_name(server, player).await;
+            }
+        }
+    }
+}
+
+const DEFAULT_PREVIEW_TEXT: &str = "The quick brown fox jumps over the lazy dog";
+
+/// `/chatcolor preview <color> [text]` : montre le style sans rien modifier
+pub struct ChatColorPreviewExecutor;
+
+#[async_trait]
+impl CommandExecutor for ChatColorPreviewExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+
+        let color_str = match args.get(ARG_COLOR) {
+            Some(Arg::Simple(s)) => *s,
+            _ => "",
+        };
+        let text = match args.get(ARG_TEXT) {
+            Some(Arg::Msg(text)) => text.as_str(),
+            _ => DEFAULT_PREVIEW_TEXT,
+        };
+
+        let Some(style) = resolve_style(color_str).await else {
+            p.send_system_message(
+                &TextComponent::text("Unknown color or style. Available: ").add_child(styled_color_list().await)
+            ).await;
+            return Ok(());
+        };
+
+        // Aperçu sur le pseudo du joueur et sur le texte, envoyé au joueur seulement
+        let preview = TextComponent::text(format!("Preview of {}: ", display_name(color_str).await))
+            .add_child(TextComponent::text("<"))
+            .add_child(apply_style(&p.gameprofile.name, &style).await)
+            .add_child(TextComponent::text("> "))
+            .add_child(apply_style(text, &style).await);
+        p.send_system_message(&preview).await;
+        Ok(())
+    }
+}
+
+/// Presets que le joueur peut appliquer, chacun avec son nom affiché
+async fn usable_presets(p: &pumpkin::entity::player::Player) -> Vec<(String, String)> {
+    let presets: Vec<(String, String)> = {
+        let config = PLUGIN_CONFIG.lock().await;
+        preset_names(&config).into_iter()
+            .map(|name| {
+                let label = preset_display_name(&name, &config.presets[&name]);
+                (name, label)
+            })
+            .collect()
+    };
+    let mut usable = Vec::new();
+    for (name, label) in presets {
+        if p.has_permission(&preset_permission(&name)).await {
+            usable.push((name, label));
+        }
+    }
+    usable
+}
+
+/// `/chatcolor preset <name>` : couleur de chat, couleur de pseudo et mise en forme en une fois
+pub struct ChatColorPresetExecutor;
+
+#[async_trait]
+impl CommandExecutor for ChatColorPresetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+
+        let preset_name = match args.get(ARG_PRESET) {
+            Some(Arg::Simple(s)) => s.to_lowercase(),
+            _ => String::new(),
+        };
+        let preset = PLUGIN_CONFIG.lock().await.presets.get(&preset_name).cloned();
+
+        let Some(preset) = preset else {
+            let names: Vec<String> = usable_presets(&p).await.into_iter().map(|(_, label)| label).collect();
+            let message = match (preset_name.is_empty(), names.is_empty()) {
+                (_, true) => "No preset available.".to_string(),
+                (true, false) => format!("Available presets: {}", names.join(", ")),
+                (false, false) => format!("Unknown preset. Available: {}", names.join(", ")),
+            };
+            p.send_system_message(&TextComponent::text(message)).await;
+            return Ok(());
+        };
+
+        if !p.has_permission(&preset_permission(&preset_name)).await {
+            p.send_system_message(&TextComponent::text(format!(
+                "You don't have permission to use the {} preset ({}).",
+                preset_name, preset_permission(&preset_name)
+            ))).await;
+            return Ok(());
+        }
+
+        // Toutes les couleurs du preset doivent exister (et être de saison) avant de toucher au joueur
+        let chat_style = match &preset.chat {
+            Some(color_name) => match resolve_style(color_name).await {
+                Some(style) => Some(style),
+                None => {
+                    p.send_system_message(&TextComponent::text("This preset is not available right now.")).await;
+                    return Ok(());
+                }
+            },
+            None => None,
+        };
+        let name_style = match &preset.name {
+            Some(color_name) => match resolve_style(color_name).await {
+                Some(style) => Some(style),
+                None => {
+                    p.send_system_message(&TextComponent::text("This preset is not available right now.")).await;
+                    return Ok(());
+                }
+            },
+            None => None,
+        };
+        if let Some(prefix) = &preset.prefix {
+            if !PLUGIN_CONFIG.lock().await.prefixes.contains_key(prefix) {
+                log::warn!("[ChatColor] Preset {} uses unknown prefix {}", preset_name, prefix);
+                p.send_system_message(&TextComponent::text("This preset is not available right now.")).await;
+                return Ok(());
+            }
+        }
+        // Même garde de lisibilité que /chatcolor et /namecolor
+        for style in chat_style.iter().chain(name_style.iter()) {
+            if !is_style_readable(style).await {
+                p.send_system_message(&TextComponent::text(
+                    "This preset is too dark to be read in chat."
+                )).await;
+                return Ok(());
+            }
+        }
+
+        let uuid = p.gameprofile.id;
+        if let Some(style) = chat_style {
+            PLAYER_COLORS.lock().await.insert(uuid, style);
+        }
+        if let Some(style) = name_style {
+            PLAYER_NAME_COLORS.lock().await.insert(uuid, style);
+        }
+        // Le préfixe et la mise en forme suivent le preset : ceux d'un ancien preset sont retirés.
+        // Le préfixe attribué par un admin (/chattag) reste prioritaire.
+        match &preset.prefix {
+            Some(prefix) => PLAYER_PRESET_PREFIXES.lock().await.insert(uuid, prefix.clone()),
+            None => PLAYER_PRESET_PREFIXES.lock().await.remove(&uuid),
+        };
+        match &preset.name_format {
+            Some(codes) => PLAYER_NAME_FORMATS.lock().await.insert(uuid, codes.clone()),
+            None => PLAYER_NAME_FORMATS.lock().await.remove(&uuid),
+        };
+        match &preset.message_format {
+            Some(codes) => PLAYER_MESSAGE_FORMATS.lock().await.insert(uuid, codes.clone()),
+            None => PLAYER_MESSAGE_FORMATS.lock().await.remove(&uuid),
+        };
+
+        // Retour avec un aperçu du pseudo et d'un message
+        let preview = TextComponent::text(format!("Preset {} applied: ", preset_display_name(&preset_name, &preset)))
+            .add_child(TextComponent::text("<"))
+            .add_child(styled_player_name(&p).await)
+            .add_child(TextComponent::text("> "))
+            .add_child(styled_player_message(&p, DEFAULT_PREVIEW_TEXT, None).await);
+        p.send_system_message(&preview).await;
+
+        apply_name_team(server, &p).await;
+        update_tab_name(server, &p).await;
+
+        if let Err(e) = save_data().await {
+            log::error!("[ChatColor] Failed to save player data: {}", e);
+        }
+        Ok(())
+    }
+}
+
 #[allow(clippy::redundant_closure_for_method_calls)]
 pub fn init_command_tree() -> CommandTree {
-    CommandTree::new(NAMES, DESCRIPTION).then(
+    CommandTree::new(NAMES, DESCRIPTION)
+    // Utilisable depuis la console (récompenses, boutique...)
+    .then(
+        literal("grant").then(
+            argument(ARG_PLAYER, SimpleArgConsumer).then(
+                argument(ARG_COLOR, PaletteArgConsumer)
+                    .then(argument(ARG_DURATION, SimpleArgConsumer).execute(ChatColorGrantExecutor)),
+            ),
+        ),
+    )
+    .then(
+        literal("unlock").then(
+            argument(ARG_PLAYER, SimpleArgConsumer)
+                .then(argument(ARG_COLOR, PaletteArgConsumer).execute(ChatColorUnlockExecutor(UnlockAction::Unlock))),
+        ),
+    )
+    .then(
+        literal("lock").then(
+            argument(ARG_PLAYER, SimpleArgConsumer)
+                .then(argument(ARG_COLOR, PaletteArgConsumer).execute(ChatColorUnlockExecutor(UnlockAction::Lock))),
+        ),
+    )
+    .then(
         require(|sender| sender.is_player())
             .execute(ChatColorExecutor)
-            .then(argument(ARG_COLOR, SimpleArgConsumer).execute(ChatColorExecutor))
+            .then(
+                literal("menu")
+                    .execute(ChatColorMenuExecutor)
+                    .then(argument(ARG_PAGE, SimpleArgConsumer).execute(ChatColorMenuExecutor)),
+            )
+            .then(
+                literal("gui")
+                    .execute(ChatColorGuiExecutor)
+                    .then(argument(ARG_TAB, SimpleArgConsumer).execute(ChatColorGuiExecutor)),
+            )
+            .then(
+                literal("custom")
+                    .then(argument(ARG_STOPS, MessageArgConsumer).execute(ChatColorCustomExecutor)),
+            )
+            .then(
+                literal("accessibility")
+                    .then(argument(ARG_MODE, SimpleArgConsumer).execute(ChatColorAccessibilityExecutor)),
+            )
+            .then(
+                literal("toggle").then(literal("others").execute(ChatColorToggleOthersExecutor)),
+            )
+            .then(
+                literal("preset")
+                    .execute(ChatColorPresetExecutor)
+                    .then(argument(ARG_PRESET, SimpleArgConsumer).execute(ChatColorPresetExecutor)),
+            )
+            .then(
+                literal("preview").then(
+                    argument(ARG_COLOR, PaletteArgConsumer)
+                        .execute(ChatColorPreviewExecutor)
+                        .then(argument(ARG_TEXT, MessageArgConsumer).execute(ChatColorPreviewExecutor)),
+                ),
+            )
+            .then(argument(ARG_COLOR, PaletteArgConsumer).execute(ChatColorExecutor))
     )
 } 
\ No newline at end of file
@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use pumpkin::command::{
+    args::{Arg, ConsumedArgs, players::PlayersArgConsumer, simple::SimpleArgConsumer},
+    dispatcher::CommandError,
+    dispatcher::CommandError::InvalidConsumption,
+    tree::CommandTree,
+    tree::builder::{argument, literal},
+    CommandExecutor, CommandSender,
+};
+use pumpkin_util::text::TextComponent;
+use crate::storage::{PLAYER_PREFIXES, PLAYER_SUFFIXES, PLUGIN_CONFIG};
+use crate::utils::{get_tag_config, save_data, update_tab_name, TagKind};
+
+const NAMES: [&str; 1] = ["chattag"];
+const DESCRIPTION: &str = "Assign or clear a player's chat prefix / suffix.";
+const ARG_TARGETS: &str = "targets";
+const ARG_TAG: &str = "tag";
+
+pub struct TagSetExecutor(TagKind);
+
+#[async_trait]
+impl CommandExecutor for TagSetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Players(targets)) = args.get(ARG_TARGETS) else {
+            return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+        let Some(Arg::Simple(tag_key)) = args.get(ARG_TAG) else {
+            return Err(InvalidConsumption(Some(ARG_TAG.into())));
+        };
+        let tag_key = tag_key.to_lowercase();
+
+        if get_tag_config(self.0, &tag_key).await.is_none() {
+            let available = {
+                let config = PLUGIN_CONFIG.lock().await;
+                let tags = match self.0 {
+                    TagKind::Prefix => &config.prefixes,
+                    TagKind::Suffix => &config.suffixes,
+                };
+                tags.keys().cloned().collect::<Vec<_>>().join(", ")
+            };
+            sender.send_message(TextComponent::text(
+                format!("Unknown {} '{}'. Available: {}", self.0.as_str(), tag_key, available)
+            )).await;
+            return Ok(());
+        }
+
+        {
+            let mut map = match self.0 {
+                TagKind::Prefix => PLAYER_PREFIXES.lock().await,
+                TagKind::Suffix => PLAYER_SUFFIXES.lock().await,
+            };
+            for target in targets {
+                map.insert(target.gameprofile.id, tag_key.clone());
+            }
+        }
+
+        for target in targets {
+            update_tab_name(server, target).await;
+            sender.send_message(TextComponent::text(
+                format!("{} of {} set to {}", self.0.as_str(), target.gameprofile.name, tag_key)
+            )).await;
+        }
+
+        if let Err(e) = save_data().await {
+            log::error!("[ChatColor] Failed to save player data: {}", e);
+        }
+        Ok(())
+    }
+}
+
+pub struct TagClearExecutor(TagKind);
+
+#[async_trait]
+impl CommandExecutor for TagClearExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Players(targets)) = args.get(ARG_TARGETS) else {
+            return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+
+        {
+            let mut map = match self.0 {
+                TagKind::Prefix => PLAYER_PREFIXES.lock().await,
+                TagKind::Suffix => PLAYER_SUFFIXES.lock().await,
+            };
+            for target in targets {
+                map.remove(&target.gameprofile.id);
+            }
+        }
+
+        // Le joueur retombe sur le tag donné par ses permissions, s'il en a un
+        for target in targets {
+            update_tab_name(server, target).await;
+            sender.send_message(TextComponent::text(
+                format!("{} of {} cleared", self.0.as_str(), target.gameprofile.name)
+            )).await;
+        }
+
+        if let Err(e) = save_data().await {
+            log::error!("[ChatColor] Failed to save player data: {}", e);
+        }
+        Ok(())
+    }
+}
+
+fn tag_subcommand(kind: TagKind) -> pumpkin::command::tree::builder::NonLeafNodeBuilder {
+    literal(kind.as_str())
+        .then(
+            literal("set").then(
+                argument(ARG_TARGETS, PlayersArgConsumer)
+                    .then(argument(ARG_TAG, SimpleArgConsumer).execute(TagSetExecutor(kind))),
+            ),
+        )
+        .then(
+            literal("clear").then(argument(ARG_TARGETS, PlayersArgConsumer).execute(TagClearExecutor(kind))),
+        )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(tag_subcommand(TagKind::Prefix))
+        .then(tag_subcommand(TagKind::Suffix))
+}
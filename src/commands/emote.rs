@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use pumpkin::command::{
+    args::{Arg, ConsumedArgs, message::MessageArgConsumer},
+    dispatcher::CommandError,
+    dispatcher::CommandError::{InvalidConsumption, InvalidRequirement},
+    tree::CommandTree,
+    tree::builder::{argument, require},
+    CommandExecutor, CommandSender,
+};
+use pumpkin_util::text::TextComponent;
+use crate::storage::PLUGIN_CONFIG;
+use crate::commands::chatcolor::get_style_from_config;
+use crate::handlers::deliver_chat_line;
+use crate::utils::{
+    apply_style, filter_player_codes, parse_color_codes, render_template, resolve_placeholders,
+    send_to_viewer, strip_color_codes, styled_player_message, styled_player_name,
+};
+
+const ME_NAMES: [&str; 1] = ["me"];
+const ME_DESCRIPTION: &str = "Describe an action in chat.";
+const SAY_NAMES: [&str; 1] = ["say"];
+const SAY_DESCRIPTION: &str = "Broadcast a message to all players.";
+const ARG_MESSAGE: &str = "message";
+
+pub struct MeExecutor;
+
+#[async_trait]
+impl CommandExecutor for MeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+        let Some(Arg::Msg(action)) = args.get(ARG_MESSAGE) else {
+            return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
+        };
+
+        let (me_format, emote_color) = {
+            let config = PLUGIN_CONFIG.lock().await;
+            (config.emotes.me_format.clone(), config.emotes.emote_color.clone())
+        };
+
+        // La couleur d'emote de la config prime sur la couleur de chat du joueur
+        let formatted_action = match emote_color {
+            Some(color_name) => match get_style_from_config(&color_name).await {
+                Some(style) => apply_style(&filter_player_codes(&p, action).await, &style).await,
+                None => styled_player_message(&p, action, None).await,
+            },
+            None => styled_player_message(&p, action, None).await,
+        };
+
+        let values = resolve_placeholders(&me_format, &p).await;
+        let line = render_template(
+            &me_format,
+            &[("name", styled_player_name(&p).await), ("message", formatted_action)],
+            &values,
+        );
+        // Version vanilla pour ceux qui ont désactivé les couleurs des autres joueurs
+        let plain_line = TextComponent::text(format!("* {} {}", p.gameprofile.name, strip_color_codes(action)));
+        for player in server.get_all_players().await {
+            deliver_chat_line(&player, &p, &line, &plain_line).await;
+        }
+
+        log::info!("<me> {}", line.get_text());
+        Ok(())
+    }
+}
+
+pub struct SayExecutor;
+
+#[async_trait]
+impl CommandExecutor for SayExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Msg(message)) = args.get(ARG_MESSAGE) else {
+            return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
+        };
+
+        let say_format = PLUGIN_CONFIG.lock().await.emotes.say_format.clone();
+
+        // La console peut aussi utiliser /say
+        let line = match sender.as_player() {
+            Some(p) => {
+                let values = resolve_placeholders(&say_format, &p).await;
+                let line = render_template(
+                    &say_format,
+                    &[
+                        ("name", styled_player_name(&p).await),
+                        ("message", styled_player_message(&p, message, None).await),
+                    ],
+                    &values,
+                );
+                // Version vanilla pour ceux qui ont désactivé les couleurs des autres joueurs
+                let plain_line = TextComponent::text(format!("[{}] {}", p.gameprofile.name, strip_color_codes(message)));
+                for player in server.get_all_players().await {
+                    deliver_chat_line(&player, &p, &line, &plain_line).await;
+                }
+                line
+            }
+            None => {
+                let line = render_template(
+                    &say_format,
+                    &[("name", TextComponent::text("Server")), ("message", parse_color_codes(message))],
+                    &Default::default(),
+                );
+                for player in server.get_all_players().await {
+                    send_to_viewer(&player, &line).await;
+                }
+                line
+            }
+        };
+
+        log::info!("<say> {}", line.get_text());
+        Ok(())
+    }
+}
+
+pub fn init_me_command_tree() -> CommandTree {
+    CommandTree::new(ME_NAMES, ME_DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .then(argument(ARG_MESSAGE, MessageArgConsumer).execute(MeExecutor)),
+    )
+}
+
+pub fn init_say_command_tree() -> CommandTree {
+    CommandTree::new(SAY_NAMES, SAY_DESCRIPTION)
+        .then(argument(ARG_MESSAGE, MessageArgConsumer).execute(SayExecutor))
+}
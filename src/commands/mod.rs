@@ -1,5 +1,15 @@
 pub mod chatcolor;
 pub mod namecolor;
+pub mod chattag;
+pub mod shout;
+pub mod channel;
+pub mod msg;
+pub mod emote;
 
 pub use chatcolor::{ChatColorExecutor, init_command_tree as init_chatcolor_command_tree};
-pub use namecolor::{NameColorExecutor, init_command_tree as init_namecolor_command_tree}; 
\ No newline at end of file
+pub use namecolor::{NameColorExecutor, init_command_tree as init_namecolor_command_tree};
+pub use chattag::init_command_tree as init_chattag_command_tree;
+pub use shout::init_command_tree as init_shout_command_tree;
+pub use channel::init_command_tree as init_channel_command_tree;
+pub use msg::{init_msg_command_tree, init_reply_command_tree};
+pub use emote::{init_me_command_tree, init_say_command_tree};
\ No newline at end of file
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use pumpkin::command::{
+    args::{Arg, ConsumedArgs, message::MessageArgConsumer, players::PlayersArgConsumer},
+    dispatcher::CommandError,
+    dispatcher::CommandError::{InvalidConsumption, InvalidRequirement},
+    tree::CommandTree,
+    tree::builder::{argument, require},
+    CommandExecutor, CommandSender,
+};
+use pumpkin::entity::player::Player;
+use pumpkin_util::text::TextComponent;
+use crate::storage::{LAST_CONVERSATION, PLUGIN_CONFIG};
+use crate::handlers::deliver_chat_line;
+use crate::utils::{render_template, resolve_placeholders, send_to_viewer, strip_color_codes, styled_player_message, styled_player_name};
+
+const MSG_NAMES: [&str; 3] = ["msg", "tell", "w"];
+const MSG_DESCRIPTION: &str = "Send a private message to a player.";
+const REPLY_NAMES: [&str; 2] = ["r", "reply"];
+const REPLY_DESCRIPTION: &str = "Reply to the last player you talked to in private.";
+const ARG_TARGETS: &str = "targets";
+const ARG_MESSAGE: &str = "message";
+
+/// Envoie un message privé stylé : chaque côté voit son propre format
+async fn send_private_message(sender: &Arc<Player>, target: &Arc<Player>, message: &str) {
+    let (sent_format, received_format) = {
+        let config = PLUGIN_CONFIG.lock().await;
+        (config.private_messages.sent_format.clone(), config.private_messages.received_format.clone())
+    };
+
+    let components = [
+        ("sender", styled_player_name(sender).await),
+        ("target", styled_player_name(target).await),
+        ("message", styled_player_message(sender, message, None).await),
+    ];
+
+    let sent_values = resolve_placeholders(&sent_format, sender).await;
+    let received_values = resolve_placeholders(&received_format, sender).await;
+    send_to_viewer(sender, &render_template(&sent_format, &components, &sent_values)).await;
+    // Version vanilla pour un destinataire qui a désactivé les couleurs des autres joueurs
+    let plain_line = TextComponent::text(format!("{} whispers to you: {}", sender.gameprofile.name, strip_color_codes(message)));
+    deliver_chat_line(target, sender, &render_template(&received_format, &components, &received_values), &plain_line).await;
+
+    // Chacun peut répondre à l'autre avec /r
+    {
+        let mut last = LAST_CONVERSATION.lock().await;
+        last.insert(sender.gameprofile.id, target.gameprofile.id);
+        last.insert(target.gameprofile.id, sender.gameprofile.id);
+    }
+
+    log::info!("<msg> {} -> {}: {}", sender.gameprofile.name, target.gameprofile.name, message);
+}
+
+pub struct MsgExecutor;
+
+#[async_trait]
+impl CommandExecutor for MsgExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+        let Some(Arg::Players(targets)) = args.get(ARG_TARGETS) else {
+            return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+        let Some(Arg::Msg(message)) = args.get(ARG_MESSAGE) else {
+            return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
+        };
+
+        for target in targets {
+            send_private_message(&p, target, message).await;
+        }
+        Ok(())
+    }
+}
+
+pub struct ReplyExecutor;
+
+#[async_trait]
+impl CommandExecutor for ReplyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+        let Some(Arg::Msg(message)) = args.get(ARG_MESSAGE) else {
+            return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
+        };
+
+        let partner = LAST_CONVERSATION.lock().await.get(&p.gameprofile.id).copied();
+        let Some(partner) = partner else {
+            p.send_system_message(&TextComponent::text("You have nobody to reply to.")).await;
+            return Ok(());
+        };
+        let Some(target) = server.get_player_by_uuid(partner).await else {
+            p.send_system_message(&TextComponent::text("That player is no longer online.")).await;
+            return Ok(());
+        };
+
+        send_private_message(&p, &target, message).await;
+        Ok(())
+    }
+}
+
+pub fn init_msg_command_tree() -> CommandTree {
+    CommandTree::new(MSG_NAMES, MSG_DESCRIPTION).then(
+        require(|sender| sender.is_player()).then(
+            argument(ARG_TARGETS, PlayersArgConsumer)
+                .then(argument(ARG_MESSAGE, MessageArgConsumer).execute(MsgExecutor)),
+        ),
+    )
+}
+
+pub fn init_reply_command_tree() -> CommandTree {
+    CommandTree::new(REPLY_NAMES, REPLY_DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .then(argument(ARG_MESSAGE, MessageArgConsumer).execute(ReplyExecutor)),
+    )
+}
@@ -4,79 +4,17 @@ use pumpkin::command::{
     dispatcher::CommandError,
     dispatcher::CommandError::InvalidRequirement,
     tree::CommandTree,
-    tree::builder::{argument, require},
+    tree::builder::{argument, literal, require},
     CommandExecutor, CommandSender,
 };
-use pumpkin_util::text::color::NamedColor;
-use pumpkin_util::permission::PermissionLvl;
-use crate::{storage::{PLAYER_NAME_COLORS, PLUGIN_CONFIG}, config::ChatColorStyle};
-use crate::utils::save_data;
-
-// Fonction pour parser un code couleur Minecraft en NamedColor
-fn parse_color_code_section(code: &str) -> Option<NamedColor> {
-    if code.chars().count() != 2 || !code.starts_with('§') {
-        return None;
-    }
-    
-    match code.chars().nth(1)? {
-        '0' => Some(NamedColor::Black),
-        '1' => Some(NamedColor::DarkBlue),
-        '2' => Some(NamedColor::DarkGreen),
-        '3' => Some(NamedColor::DarkAqua),
-        '4' => Some(NamedColor::DarkRed),
-        '5' => Some(NamedColor::DarkPurple),
-        '6' => Some(NamedColor::Gold),
-        '7' => Some(NamedColor::Gray),
-        '8' => Some(NamedColor::DarkGray),
-        '9' => Some(NamedColor::Blue),
-        'a' => Some(NamedColor::Green),
-        'b' => Some(NamedColor::Aqua),
-        'c' => Some(NamedColor::Red),
-        'd' => Some(NamedColor::LightPurple),
-        'e' => Some(NamedColor::Yellow),
-        'f' => Some(NamedColor::White),
-        _ => None,
-    }
-}
-
-// Fonction pour obtenir le style depuis la configuration
-async fn get_style_from_config(color_name: &str) -> Option<ChatColorStyle> {
-    let config = PLUGIN_CONFIG.lock().await;
-    let color_name = color_name.to_lowercase();
-    
-    // Vérifier d'abord les couleurs simples
-    if let Some(color_code) = config.simple_colors.get(&color_name) {
-        if let Some(named_color) = parse_color_code_section(color_code) {
-            return Some(ChatColorStyle::Simple(named_color));
-        }
-    }
-    
-    // Vérifier ensuite les gradients
-    if config.gradients.contains_key(&color_name) {
-        return Some(ChatColorStyle::CustomGradient(color_name));
-    }
-    
-    None
-}
-
-// Fonction pour obtenir la liste des couleurs disponibles
-async fn get_available_colors() -> Vec<String> {
-    let config = PLUGIN_CONFIG.lock().await;
-    let mut colors = Vec::new();
-    
-    // Ajouter les couleurs simples
-    colors.extend(config.simple_colors.keys().cloned());
-    
-    // Ajouter les gradients
-    colors.extend(config.gradients.keys().cloned());
-    
-    log::info!("[ChatColor] Available colors: {:?}", colors);
-    colors
-}
+use crate::{storage::PLAYER_NAME_COLORS, config::ChatColorStyle};
+use crate::commands::chatcolor::{get_style_from_config, PaletteArgConsumer};
+use crate::utils::{save_data, apply_name_team, apply_style, display_name, build_color_menu, can_use_color, color_permission, is_style_readable, update_tab_name};
 
 const NAMES: [&str; 1] = ["namecolor"];
 const DESCRIPTION: &str = "Set your name color or gradient (ex: red, blue, rainbow, fire).";
 const ARG_COLOR: &str = "color";
+const ARG_PAGE: &str = "page";
 
 pub struct NameColorExecutor;
 
@@ -85,26 +23,25 @@ impl CommandExecutor for NameColorExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender,
-        _server: &pumpkin::server::Server,
+        server: &pumpkin::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let Some(p) = sender.as_player() else {
             return Err(InvalidRequirement);
         };
         
-        // Seuls les OP peuvent utiliser la commande
-        if sender.permission_lvl() < PermissionLvl::One {
-            p.send_system_message(&pumpkin_util::text::TextComponent::text(
-                "You must be OP to use this command."
-            )).await;
-            return Ok(());
-        }
-
         let color_str = match args.get(ARG_COLOR) {
             Some(Arg::Simple(s)) => *s,
             _ => "",
         };
         
+        // Sans argument : menu cliquable de la palette
+        if color_str.is_empty() {
+            let current = PLAYER_NAME_COLORS.lock().await.get(&p.gameprofile.id).cloned();
+            p.send_system_message(&build_color_menu(&p, "namecolor", "Name colors", current.as_ref(), 1).await).await;
+            return Ok(());
+        }
+        
         // Essayer d'abord les styles spéciaux (pour la compatibilité)
         let style = if color_str.to_lowercase() == "rainbow" {
             Some(ChatColorStyle::Rainbow)
@@ -115,41 +52,91 @@ impl CommandExecutor for NameColorExecutor {
             get_style_from_config(&color_str.to_lowercase()).await
         };
         
+        if style.is_some() && !can_use_color(&p, color_str).await {
+            p.send_system_message(&pumpkin_util::text::TextComponent::text(format!(
+                "You don't have permission to use {} ({}).",
+                display_name(color_str).await, color_permission(color_str)
+            ))).await;
+            return Ok(());
+        }
+        
+        if let Some(style) = &style {
+            if !is_style_readable(style).await {
+                p.send_system_message(&pumpkin_util::text::TextComponent::text(
+                    "This color is too dark to be read in chat, pick a lighter one."
+                )).await;
+                return Ok(());
+            }
+        }
+        
         if let Some(style) = style {
             {
                 let mut map = PLAYER_NAME_COLORS.lock().await;
                 map.insert(p.gameprofile.id, style.clone());
             }
-            let feedback = match &style {
-                ChatColorStyle::Simple(color) => format!("Your name color is now set to {:?}", color),
-                ChatColorStyle::Rainbow => "Your name color is now set to RAINBOW! 🌈".to_string(),
-                ChatColorStyle::Fire => "Your name color is now set to FIRE! 🔥".to_string(),
-                ChatColorStyle::CustomGradient(gradient_name) => format!("Your name color is now set to {} gradient!", gradient_name),
-            };
-            p.send_system_message(&pumpkin_util::text::TextComponent::text(feedback)).await;
+            // Une couleur choisie à la main remplace la mise en forme et le préfixe d'un preset
+            crate::storage::PLAYER_NAME_FORMATS.lock().await.remove(&p.gameprofile.id);
+            crate::storage::PLAYER_PRESET_PREFIXES.lock().await.remove(&p.gameprofile.id);
+            // Retour avec le nom affiché de l'entrée, dans son propre style
+            let label = display_name(color_str).await;
+            p.send_system_message(
+                &pumpkin_util::text::TextComponent::text("Your name color is now set to ")
+                    .add_child(apply_style(&label, &style).await)
+            ).await;
+
+            // Mettre à jour le nametag au-dessus de la tête
+            apply_name_team(server, &p).await;
+            update_tab_name(server, &p).await;
             
             // Sauvegarder les données après le changement
             if let Err(e) = save_data().await {
                 log::error!("[ChatColor] Failed to save player data: {}", e);
             }
         } else {
-            // Afficher la liste des couleurs disponibles
-            let available_colors = get_available_colors().await;
-            let color_list = available_colors.join(", ");
-            p.send_system_message(&pumpkin_util::text::TextComponent::text(
-                format!("Unknown color or style. Available: {}", color_list)
-            )).await;
+            // Afficher le menu des couleurs disponibles
+            let current = PLAYER_NAME_COLORS.lock().await.get(&p.gameprofile.id).cloned();
+            p.send_system_message(&pumpkin_util::text::TextComponent::text("Unknown color or style.")).await;
+            p.send_system_message(&build_color_menu(&p, "namecolor", "Name colors", current.as_ref(), 1).await).await;
         }
         
         Ok(())
     }
 }
 
+/// `/namecolor menu <page>` : page du menu cliquable
+pub struct NameColorMenuExecutor;
+
+#[async_trait]
+impl CommandExecutor for NameColorMenuExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+        let page = match args.get(ARG_PAGE) {
+            Some(Arg::Simple(s)) => s.parse::<usize>().unwrap_or(1),
+            _ => 1,
+        };
+        let current = PLAYER_NAME_COLORS.lock().await.get(&p.gameprofile.id).cloned();
+        p.send_system_message(&build_color_menu(&p, "namecolor", "Name colors", current.as_ref(), page).await).await;
+        Ok(())
+    }
+}
+
 #[allow(clippy::redundant_closure_for_method_calls)]
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         require(|sender| sender.is_player())
             .execute(NameColorExecutor)
-            .then(argument(ARG_COLOR, SimpleArgConsumer).execute(NameColorExecutor))
+            .then(
+                literal("menu")
+                    .execute(NameColorMenuExecutor)
+                    .then(argument(ARG_PAGE, SimpleArgConsumer).execute(NameColorMenuExecutor)),
+            )
+            .then(argument(ARG_COLOR, PaletteArgConsumer).execute(NameColorExecutor))
     )
 } 
\ No newline at end of file
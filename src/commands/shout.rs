@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use pumpkin::command::{
+    args::{Arg, ConsumedArgs, message::MessageArgConsumer},
+    dispatcher::CommandError,
+    dispatcher::CommandError::{InvalidConsumption, InvalidRequirement},
+    tree::CommandTree,
+    tree::builder::{argument, require},
+    CommandExecutor, CommandSender,
+};
+use pumpkin::plugin::{player::player_chat::PlayerChatEvent, Cancellable};
+use pumpkin_util::text::TextComponent;
+use crate::handlers::{filter_scope, plain_chat_line};
+use crate::storage::{PLUGIN_CONFIG, SHOUTING};
+use crate::utils::send_to_viewer;
+
+const NAMES: [&str; 1] = ["shout"];
+const DESCRIPTION: &str = "Send a chat message to everyone when local chat is enabled.";
+const ARG_MESSAGE: &str = "message";
+
+pub struct ShoutExecutor;
+
+#[async_trait]
+impl CommandExecutor for ShoutExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &pumpkin::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(p) = sender.as_player() else {
+            return Err(InvalidRequirement);
+        };
+        let Some(Arg::Msg(message)) = args.get(ARG_MESSAGE) else {
+            return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
+        };
+        if message.trim().is_empty() {
+            p.send_system_message(&TextComponent::text("Nothing to shout.")).await;
+            return Ok(());
+        }
+
+        // Passe par l'événement de chat pour que les autres plugins (ignore, vanish, mute...) filtrent
+        // les destinataires comme pour un message normal ; ChatEventHandler l'envoie en global
+        SHOUTING.lock().await.insert(p.gameprofile.id);
+        let event = pumpkin::PLUGIN_MANAGER
+            .fire(PlayerChatEvent::new(p.clone(), message.clone(), Vec::new()))
+            .await;
+        SHOUTING.lock().await.remove(&p.gameprofile.id);
+
+        // Personne n'a mis en forme le message (joueur sans style, chat local désactivé) : version vanilla
+        if !event.cancelled() {
+            let chat_scope = PLUGIN_CONFIG.lock().await.settings.chat_scope;
+            let plain_line = plain_chat_line(&p, message);
+            for player in &filter_scope(&p, event.recipients.clone(), chat_scope).await {
+                send_to_viewer(player, &plain_line).await;
+            }
+            log::info!("<shout> {}: {}", p.gameprofile.name, plain_line.get_text());
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .then(argument(ARG_MESSAGE, MessageArgConsumer).execute(ShoutExecutor))
+    )
+}
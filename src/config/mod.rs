@@ -2,12 +2,56 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use pumpkin_util::text::color::NamedColor;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ChatColorStyle {
     Simple(NamedColor),
     Rainbow,
     Fire,
     CustomGradient(String), // Nom du gradient personnalisé
+    PersonalGradient(Vec<[u8; 3]>), // Gradient créé par le joueur avec /chatcolor custom (couleurs RGB)
+}
+
+/// Couleur de chat donnée pour une durée limitée (/chatcolor grant)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorGrant {
+    pub style: ChatColorStyle, // style donné
+    pub previous: Option<ChatColorStyle>, // style rétabli à l'expiration
+    pub expires_at: u64, // secondes depuis l'époque Unix
+}
+
+/// Mode d'accessibilité d'un joueur : transforme les couleurs des messages qu'il reçoit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessibilityMode {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    HighContrast,
+    NoColors,
+}
+
+impl AccessibilityMode {
+    pub const ALL: [AccessibilityMode; 5] = [
+        AccessibilityMode::Protanopia,
+        AccessibilityMode::Deuteranopia,
+        AccessibilityMode::Tritanopia,
+        AccessibilityMode::HighContrast,
+        AccessibilityMode::NoColors,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessibilityMode::Protanopia => "protanopia",
+            AccessibilityMode::Deuteranopia => "deuteranopia",
+            AccessibilityMode::Tritanopia => "tritanopia",
+            AccessibilityMode::HighContrast => "high_contrast",
+            AccessibilityMode::NoColors => "no_colors",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.as_str().eq_ignore_ascii_case(name))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,11 +59,58 @@ pub struct PlayerData {
     pub uuid: String,
     pub chat_color: Option<ChatColorStyle>,
     pub name_color: Option<ChatColorStyle>,
+    #[serde(default)]
+    pub prefix: Option<String>, // clé du préfixe attribué par un admin
+    #[serde(default)]
+    pub suffix: Option<String>, // clé du suffixe attribué par un admin
+    #[serde(default)]
+    pub channels: Vec<String>, // canaux rejoints
+    #[serde(default)]
+    pub focused_channel: Option<String>, // canal où partent les messages (None = global)
+    #[serde(default)]
+    pub accessibility: Option<AccessibilityMode>, // transformation des couleurs reçues
+    #[serde(default)]
+    pub hide_other_colors: bool, // voit les messages des autres joueurs sans style (vanilla)
+    #[serde(default)]
+    pub grant: Option<ColorGrant>, // couleur de chat temporaire en cours
+    #[serde(default)]
+    pub unlocked_colors: Vec<String>, // entrées de la palette débloquées sans permission
+    #[serde(default)]
+    pub name_format: Option<String>, // mise en forme du pseudo venant d'un preset
+    #[serde(default)]
+    pub message_format: Option<String>, // mise en forme des messages venant d'un preset
+    #[serde(default)]
+    pub preset_prefix: Option<String>, // clé du préfixe venant d'un preset
+}
+
+impl PlayerData {
+    pub fn new(uuid: String) -> Self {
+        PlayerData {
+            uuid,
+            chat_color: None,
+            name_color: None,
+            prefix: None,
+            suffix: None,
+            channels: Vec::new(),
+            focused_channel: None,
+            accessibility: None,
+            hide_other_colors: false,
+            grant: None,
+            unlocked_colors: Vec::new(),
+            name_format: None,
+            message_format: None,
+            preset_prefix: None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PluginData {
     pub players: HashMap<String, PlayerData>,
+    #[serde(default)]
+    pub known_players: Vec<String>, // joueurs déjà connectés (message de première connexion)
+    #[serde(default)]
+    pub player_names: HashMap<String, String>, // dernier pseudo connu par UUID (cibles hors ligne)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,6 +121,78 @@ pub enum GradientMethod {
     RgbInterpolation,
 }
 
+/// Période pendant laquelle une entrée de la palette peut être choisie.
+/// Dates `AAAA-MM-JJ`, ou `MM-JJ` pour une période qui revient chaque année.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Availability {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_until: Option<String>,
+}
+
+/// Informations d'affichage d'une entrée de la palette (listes, menus, messages)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntryMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>, // nom affiché à la place de la clé (ex: "Dark Aqua")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>, // les entrées sont regroupées par catégorie
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub sort_order: i32, // ordre dans la catégorie (plus petit = en premier)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>, // objet du sélecteur en coffre (ex: "minecraft:diamond")
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool, // absente des listes mais utilisable par son nom
+}
+
+fn is_zero(value: &i32) -> bool {
+    *value == 0
+}
+
+/// Couleur simple de la palette : un code (`"§c"`) ou un code avec une période de disponibilité
+/// et des informations d'affichage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SimpleColorConfig {
+    Code(String),
+    Detailed(SimpleColorEntry),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimpleColorEntry {
+    pub code: String,
+    #[serde(flatten)]
+    pub availability: Availability,
+    #[serde(flatten)]
+    pub meta: EntryMeta,
+}
+
+impl SimpleColorConfig {
+    pub fn code(&self) -> &str {
+        match self {
+            SimpleColorConfig::Code(code) => code,
+            SimpleColorConfig::Detailed(entry) => &entry.code,
+        }
+    }
+
+    pub fn availability(&self) -> Option<&Availability> {
+        match self {
+            SimpleColorConfig::Code(_) => None,
+            SimpleColorConfig::Detailed(entry) => Some(&entry.availability),
+        }
+    }
+
+    pub fn meta(&self) -> Option<&EntryMeta> {
+        match self {
+            SimpleColorConfig::Code(_) => None,
+            SimpleColorConfig::Detailed(entry) => Some(&entry.meta),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GradientConfig {
     #[serde(rename = "type")]
@@ -44,19 +207,302 @@ pub struct GradientConfig {
     pub value: Option<f32>,
     #[serde(default)]
     pub colors: Option<Vec<[u8; 3]>>,
+    #[serde(flatten)]
+    pub availability: Availability,
+    #[serde(flatten)]
+    pub meta: EntryMeta,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PluginConfig {
     pub save_interval: u64, // en secondes
     pub auto_save: bool,
-    pub simple_colors: HashMap<String, String>,
+    pub simple_colors: HashMap<String, SimpleColorConfig>,
     pub gradients: HashMap<String, GradientConfig>,
+    #[serde(default)]
+    pub local_chat: LocalChatConfig,
+    #[serde(default)]
+    pub channels: HashMap<String, ChannelConfig>,
+    #[serde(default)]
+    pub private_messages: PrivateMessageConfig,
+    #[serde(default)]
+    pub emotes: EmoteConfig,
+    #[serde(default)]
+    pub messages: MessagesConfig,
+    #[serde(default)]
+    pub readability: ReadabilityConfig,
+    #[serde(default)]
+    pub prefixes: HashMap<String, TagConfig>,
+    #[serde(default)]
+    pub suffixes: HashMap<String, TagConfig>,
+    #[serde(default)]
+    pub presets: HashMap<String, PresetConfig>,
     pub settings: Settings,
 }
 
+/// Chat de proximité : les messages ne sont reçus qu'autour de l'expéditeur
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalChatConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_local_radius")]
+    pub radius: f64, // en blocs
+    #[serde(default = "default_shout_prefix")]
+    pub shout_prefix: String, // préfixe pour parler à tout le monde
+}
+
+impl Default for LocalChatConfig {
+    fn default() -> Self {
+        LocalChatConfig {
+            enabled: false,
+            radius: default_local_radius(),
+            shout_prefix: default_shout_prefix(),
+        }
+    }
+}
+
+fn default_local_radius() -> f64 {
+    100.0
+}
+
+fn default_shout_prefix() -> String {
+    "!".to_string()
+}
+
+/// Canal de chat nommé (staff, trade, ...) dont les messages ne vont qu'aux membres
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelConfig {
+    #[serde(default)]
+    pub prefix: String, // affiché devant chaque message du canal, codes & autorisés
+    #[serde(default)]
+    pub color: Option<String>, // couleur par défaut des messages si le joueur n'en a pas
+    #[serde(default)]
+    pub permission: Option<String>, // nécessaire pour rejoindre, lire et écrire
+}
+
+/// Messages privés colorés (/msg, /tell, /r)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateMessageConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool, // remplace les commandes /msg, /tell et /r du serveur
+    #[serde(default = "default_whisper_sent_format")]
+    pub sent_format: String, // vu par l'expéditeur ({sender}, {target}, {message})
+    #[serde(default = "default_whisper_received_format")]
+    pub received_format: String, // vu par le destinataire ({sender}, {target}, {message})
+}
+
+impl Default for PrivateMessageConfig {
+    fn default() -> Self {
+        PrivateMessageConfig {
+            enabled: true,
+            sent_format: default_whisper_sent_format(),
+            received_format: default_whisper_received_format(),
+        }
+    }
+}
+
+fn default_whisper_sent_format() -> String {
+    "&7[me -> {target}&7] &r{message}".to_string()
+}
+
+fn default_whisper_received_format() -> String {
+    "&7[{sender}&7 -> me] &r{message}".to_string()
+}
+
+/// /me et /say rendus avec les styles du plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmoteConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool, // remplace les commandes /me et /say du serveur
+    #[serde(default = "default_me_format")]
+    pub me_format: String, // {name}, {message}
+    #[serde(default = "default_say_format")]
+    pub say_format: String, // {name}, {message}
+    #[serde(default)]
+    pub emote_color: Option<String>, // couleur de l'action /me, sinon la couleur de chat du joueur
+}
+
+impl Default for EmoteConfig {
+    fn default() -> Self {
+        EmoteConfig {
+            enabled: true,
+            me_format: default_me_format(),
+            say_format: default_say_format(),
+            emote_color: None,
+        }
+    }
+}
+
+fn default_me_format() -> String {
+    "* {name}&r {message}".to_string()
+}
+
+fn default_say_format() -> String {
+    "[{name}&r] {message}".to_string()
+}
+
+/// Contraste minimum des couleurs choisies par rapport au fond du chat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadabilityConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_min_contrast")]
+    pub min_contrast: f64, // rapport de contraste WCAG (1 à 21)
+    #[serde(default = "default_chat_background")]
+    pub background: String, // couleur hexadécimale du fond du chat
+    #[serde(default)]
+    pub mode: ReadabilityMode,
+}
+
+impl Default for ReadabilityConfig {
+    fn default() -> Self {
+        ReadabilityConfig {
+            enabled: false,
+            min_contrast: default_min_contrast(),
+            background: default_chat_background(),
+            mode: ReadabilityMode::default(),
+        }
+    }
+}
+
+impl ReadabilityConfig {
+    pub fn background_rgb(&self) -> (u8, u8, u8) {
+        crate::utils::parse_hex_color(&self.background).map_or((0, 0, 0), |c| (c[0], c[1], c[2]))
+    }
+}
+
+fn default_min_contrast() -> f64 {
+    3.0
+}
+
+fn default_chat_background() -> String {
+    "#000000".to_string()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadabilityMode {
+    Reject, // les couleurs trop sombres ne peuvent pas être choisies
+    #[default]
+    Lighten, // les couleurs trop sombres sont éclaircies à l'affichage
+}
+
+/// Messages de connexion, déconnexion et mort
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessagesConfig {
+    #[serde(default)]
+    pub join: Option<String>, // None = message vanilla, "" = aucun message
+    #[serde(default)]
+    pub first_join: Option<String>, // première connexion, sinon `join` est utilisé
+    #[serde(default)]
+    pub quit: Option<String>, // None = message vanilla, "" = aucun message
+    #[serde(default)]
+    pub death: DeathMessageMode,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeathMessageMode {
+    #[default]
+    Vanilla, // message inchangé
+    Restyle, // pseudos de la victime et du tueur avec leur couleur
+    Hide, // aucun message de mort
+}
+
+/// Préfixe ou suffixe (tag de rang) affiché autour du pseudo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagConfig {
+    pub text: String, // peut contenir des codes &
+    #[serde(default)]
+    pub color: Option<String>, // couleur simple ou gradient de la palette
+    #[serde(default)]
+    pub permission: Option<String>, // attribué automatiquement aux joueurs ayant cette permission
+    #[serde(default)]
+    pub priority: i32, // le tag de plus haute priorité gagne
+}
+
+/// Preset appliqué avec /chatcolor preset : couleur de chat, couleur de pseudo et mise en forme ensemble
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PresetConfig {
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub chat: Option<String>, // couleur simple ou gradient de la palette
+    #[serde(default)]
+    pub name: Option<String>, // couleur simple ou gradient de la palette
+    #[serde(default)]
+    pub name_format: Option<String>, // codes de mise en forme du pseudo (ex: "&l")
+    #[serde(default)]
+    pub message_format: Option<String>, // codes de mise en forme des messages
+    #[serde(default)]
+    pub prefix: Option<String>, // clé d'un préfixe de la config
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Settings {
     pub default_chat_color: String,
     pub default_name_color: String,
+    #[serde(default = "default_true")]
+    pub nametag_colors: bool, // colore le nametag au-dessus de la tête via les équipes scoreboard
+    #[serde(default = "default_chat_format")]
+    pub chat_format: String, // template de la ligne de chat ({prefix}, {name}, {suffix}, {message})
+    #[serde(default)]
+    pub chat_scope: ChatScope, // à qui sont envoyés les messages colorés
+    #[serde(default)]
+    pub chat_priority: HandlerPriority, // priorité du handler de chat face aux autres plugins
+    #[serde(default = "default_blocked_codes")]
+    pub blocked_codes: Vec<char>, // codes & interdits aux joueurs (chat, panneaux, objets, livres)
+    #[serde(default = "default_max_custom_stops")]
+    pub max_custom_stops: usize, // nombre maximum de couleurs d'un gradient /chatcolor custom
+    #[serde(default = "default_true")]
+    pub keep_seasonal_styles: bool, // garde les couleurs saisonnières choisies après la fin de leur période
+}
+
+fn default_max_custom_stops() -> usize {
+    5
+}
+
+fn default_blocked_codes() -> Vec<char> {
+    vec!['k'] // obfuscation
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HandlerPriority {
+    Lowest,
+    Low,
+    #[default]
+    Normal,
+    High,
+    Highest,
+}
+
+impl HandlerPriority {
+    pub fn to_event_priority(self) -> pumpkin::plugin::EventPriority {
+        use pumpkin::plugin::EventPriority;
+        match self {
+            HandlerPriority::Lowest => EventPriority::Lowest,
+            HandlerPriority::Low => EventPriority::Low,
+            HandlerPriority::Normal => EventPriority::Normal,
+            HandlerPriority::High => EventPriority::High,
+            HandlerPriority::Highest => EventPriority::Highest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ChatScope {
+    #[default]
+    #[serde(rename = "server")]
+    Server, // tous les joueurs en ligne, comme le chat vanilla
+    #[serde(rename = "world")]
+    World, // seulement les joueurs du monde de l'expéditeur
+}
+
+fn default_chat_format() -> String {
+    crate::utils::DEFAULT_CHAT_FORMAT.to_string()
+}
+
+fn default_true() -> bool {
+    true
 } 
\ No newline at end of file
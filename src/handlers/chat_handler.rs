@@ -1,92 +1,234 @@
 use std::sync::Arc;
+use pumpkin::entity::player::Player;
 use pumpkin::plugin::{
     player::player_chat::PlayerChatEvent,
     EventHandler, Cancellable
 };
-use pumpkin_util::text::color::NamedColor;
-use crate::{storage::{PLAYER_COLORS, PLAYER_NAME_COLORS, PLUGIN_CONFIG}, config::ChatColorStyle};
-use crate::utils::{apply_rainbow_gradient, apply_fire_gradient, parse_color_codes, apply_custom_gradient, color_to_code};
+use pumpkin_util::text::TextComponent;
+use crate::{storage::{HIDE_OTHER_COLORS, PLAYER_COLORS, PLAYER_MESSAGE_FORMATS, PLAYER_NAME_COLORS, PLAYER_NAME_FORMATS, PLUGIN_CONFIG, SHOUTING}, config::{ChannelConfig, ChatColorStyle, ChatScope}};
+use crate::utils::{
+    channel_members, expand_placeholders, strip_color_codes, focused_channel, parse_color_codes, render_template,
+    render_tag, resolve_placeholders, resolve_tag, send_to_viewer, styled_player_message, styled_player_name, TagKind,
+};
 use crate::commands::chatcolor::get_style_from_config;
 
+/// Indique si le joueur a un style ou un tag qui modifie l'affichage vanilla
+pub async fn has_chat_style(player: &Player) -> bool {
+    PLAYER_COLORS.lock().await.contains_key(&player.gameprofile.id)
+        || PLAYER_NAME_COLORS.lock().await.contains_key(&player.gameprofile.id)
+        || PLAYER_NAME_FORMATS.lock().await.contains_key(&player.gameprofile.id)
+        || PLAYER_MESSAGE_FORMATS.lock().await.contains_key(&player.gameprofile.id)
+        || resolve_tag(player, TagKind::Prefix).await.is_some()
+        || resolve_tag(player, TagKind::Suffix).await.is_some()
+}
+
+/// Construit la ligne de chat complète (préfixe, pseudo, suffixe, message) d'après le template de la config.
+/// `fallback_style` colore le message quand le joueur n'a pas de couleur de chat (ex: couleur d'un canal).
+pub async fn build_chat_line(player: &Player, message: &str, fallback_style: Option<ChatColorStyle>) -> TextComponent {
+    let formatted_message = styled_player_message(player, message, fallback_style).await;
+    let formatted_name = styled_player_name(player).await;
+
+    let formatted_prefix = match resolve_tag(player, TagKind::Prefix).await {
+        Some(tag) => render_tag(&tag, player).await,
+        None => TextComponent::text(""),
+    };
+    let formatted_suffix = match resolve_tag(player, TagKind::Suffix).await {
+        Some(tag) => render_tag(&tag, player).await,
+        None => TextComponent::text(""),
+    };
+
+    // Construit la ligne complète à partir du template de la config
+    let chat_format = PLUGIN_CONFIG.lock().await.settings.chat_format.clone();
+    let values = resolve_placeholders(&chat_format, player).await;
+    render_template(
+        &chat_format,
+        &[
+            ("prefix", formatted_prefix),
+            ("name", formatted_name),
+            ("suffix", formatted_suffix),
+            ("message", formatted_message),
+        ],
+        &values,
+    )
+}
+
+/// Ligne de chat vanilla, sans couleurs ni tags : `<pseudo> message`
+pub fn plain_chat_line(player: &Player, message: &str) -> TextComponent {
+    TextComponent::text(format!("<{}> {}", player.gameprofile.name, strip_color_codes(message)))
+}
+
+/// Envoie une ligne de chat à un destinataire : la version vanilla s'il a désactivé
+/// les couleurs des autres joueurs (ses propres messages restent stylés)
+pub async fn deliver_chat_line(viewer: &Player, sender: &Player, chat_line: &TextComponent, plain_line: &TextComponent) {
+    let plain = viewer.gameprofile.id != sender.gameprofile.id
+        && HIDE_OTHER_COLORS.lock().await.contains(&viewer.gameprofile.id);
+    send_to_viewer(viewer, if plain { plain_line } else { chat_line }).await;
+}
+
+/// Garde seulement les joueurs du même monde que l'expéditeur et à moins de `radius` blocs
+async fn filter_in_range(sender: &Player, players: Vec<Arc<Player>>, radius: f64) -> Vec<Arc<Player>> {
+    let sender_world = sender.living_entity.entity.world.read().await.clone();
+    let sender_pos = sender.living_entity.entity.pos.load();
+    let radius_sq = radius * radius;
+
+    let mut in_range = Vec::new();
+    for player in players {
+        if !Arc::ptr_eq(&*player.living_entity.entity.world.read().await, &sender_world) {
+            continue;
+        }
+        let pos = player.living_entity.entity.pos.load();
+        let (dx, dy, dz) = (pos.x - sender_pos.x, pos.y - sender_pos.y, pos.z - sender_pos.z);
+        if dx * dx + dy * dy + dz * dz <= radius_sq {
+            in_range.push(player);
+        }
+    }
+    in_range
+}
+
+/// Envoie un message sur un canal : préfixe du canal + ligne de chat, aux seuls membres
+/// qui figurent aussi dans `recipients` (les destinataires de l'événement de chat).
+pub async fn send_to_channel(
+    server: &pumpkin::server::Server,
+    player: &Player,
+    message: &str,
+    channel_name: &str,
+    channel: &ChannelConfig,
+    recipients: &[Arc<Player>],
+) {
+    let fallback_style = match &channel.color {
+        Some(color_name) => get_style_from_config(color_name).await,
+        None => None,
+    };
+    let channel_prefix = parse_color_codes(&expand_placeholders(&channel.prefix, player).await);
+    let chat_line = TextComponent::text("")
+        .add_child(channel_prefix.clone())
+        .add_child(build_chat_line(player, message, fallback_style).await);
+    let plain_line = TextComponent::text("")
+        .add_child(channel_prefix)
+        .add_child(plain_chat_line(player, message));
+
+    for member in channel_members(server, channel_name, channel).await {
+        if recipients.iter().any(|r| r.gameprofile.id == member.gameprofile.id) {
+            deliver_chat_line(&member, player, &chat_line, &plain_line).await;
+        }
+    }
+
+    log::info!("<chat:{}> {}: {}", channel_name, player.gameprofile.name, chat_line.get_text());
+}
+
+/// Garde seulement les destinataires du monde de l'expéditeur si le scope de la config est `world`
+pub async fn filter_scope(sender: &Player, recipients: Vec<Arc<Player>>, chat_scope: ChatScope) -> Vec<Arc<Player>> {
+    match chat_scope {
+        ChatScope::Server => recipients,
+        ChatScope::World => {
+            let sender_world = sender.living_entity.entity.world.read().await.clone();
+            let mut in_world = Vec::new();
+            for player in recipients {
+                if Arc::ptr_eq(&*player.living_entity.entity.world.read().await, &sender_world) {
+                    in_world.push(player);
+                }
+            }
+            in_world
+        }
+    }
+}
+
+/// Exécuté avant tous les autres handlers de chat : une liste de destinataires vide (message pas
+/// encore filtré, "tout le monde") est remplacée par tous les joueurs en ligne. Les plugins suivants
+/// (ignore, vanish...) filtrent donc une liste explicite, et une liste vide ensuite veut dire "personne".
+pub struct ChatRecipientsHandler;
+
+#[async_trait::async_trait]
+impl EventHandler<PlayerChatEvent> for ChatRecipientsHandler {
+    async fn handle_blocking(
+        &self,
+        server: &Arc<pumpkin::server::Server>,
+        event: &mut PlayerChatEvent,
+    ) {
+        if event.recipients.is_empty() {
+            event.recipients = server.get_all_players().await;
+        }
+    }
+}
+
 pub struct ChatEventHandler;
 
 #[async_trait::async_trait]
 impl EventHandler<PlayerChatEvent> for ChatEventHandler {
     async fn handle_blocking(
         &self,
-        _server: &Arc<pumpkin::server::Server>,
+        server: &Arc<pumpkin::server::Server>,
         event: &mut PlayerChatEvent,
     ) {
-        log::info!("ChatColor: handle_blocking called for message: '{}'", event.message);
-
-        // Récupère la couleur du joueur (pas de fallback)
-        let player_style = {
-            let map = PLAYER_COLORS.lock().await;
-            map.get(&event.player.gameprofile.id).cloned()
-        };
-        let player_name_style = {
-            let map = PLAYER_NAME_COLORS.lock().await;
-            map.get(&event.player.gameprofile.id).cloned()
+        let (chat_scope, local_chat) = {
+            let config = PLUGIN_CONFIG.lock().await;
+            (config.settings.chat_scope, config.local_chat.clone())
         };
 
-        // Si aucune couleur n'est définie, ne rien faire (laisser le chat vanilla)
-        if player_style.is_none() && player_name_style.is_none() {
+        // Chat local : un message commençant par le préfixe de shout (ou envoyé avec /shout) part en global
+        let mut message = event.message.clone();
+        let shouted_by_command = SHOUTING.lock().await.remove(&event.player.gameprofile.id);
+        let mut local = local_chat.enabled && !shouted_by_command;
+        if local && !local_chat.shout_prefix.is_empty() {
+            if let Some(shouted) = message.strip_prefix(&local_chat.shout_prefix) {
+                message = shouted.trim_start().to_string();
+                local = false;
+            }
+        }
+        // Un message vide (ex: seulement `!`) n'est envoyé à personne
+        if message.trim().is_empty() {
+            event.set_cancelled(true);
             return;
         }
 
-        // LOG: Afficher le style utilisé pour ce joueur
-        log::info!("[ChatColor] Style pour {}: {:?}", event.player.gameprofile.name, player_style);
-        // Formate le message
-        let formatted_message = match player_style.as_ref() {
-            Some(style) => match style {
-                ChatColorStyle::Simple(color) => {
-                    if event.message.contains('&') {
-                        parse_color_codes(&event.message)
-                    } else {
-                        parse_color_codes(&format!("&{}{}", color_to_code(*color), event.message))
-                    }
-                },
-                ChatColorStyle::Rainbow => apply_rainbow_gradient(&event.message),
-                ChatColorStyle::Fire => apply_fire_gradient(&event.message),
-                ChatColorStyle::CustomGradient(gradient_name) => apply_custom_gradient(&event.message, gradient_name).await,
-            },
-            None => parse_color_codes(&event.message),
-        };
+        // Canal de chat : le message ne va qu'aux membres du canal (le chat local ne s'applique pas, /shout l'ignore)
+        let channel = if shouted_by_command { None } else { focused_channel(&event.player).await };
+        if let Some((channel_name, channel)) = channel {
+            event.set_cancelled(true);
+            send_to_channel(server, &event.player, &event.message, &channel_name, &channel, &event.recipients).await;
+            return;
+        }
 
-        // LOG: Afficher le style utilisé pour le pseudo
-        log::info!("[ChatColor] Style PSEUDO pour {}: {:?}", event.player.gameprofile.name, player_name_style);
-        // Formate le pseudo
-        let formatted_name = match player_name_style.as_ref() {
-            Some(style) => match style {
-                ChatColorStyle::Simple(color) => {
-                    parse_color_codes(&format!("&{}{}", color_to_code(*color), event.player.gameprofile.name))
-                },
-                ChatColorStyle::Rainbow => apply_rainbow_gradient(&event.player.gameprofile.name),
-                ChatColorStyle::Fire => apply_fire_gradient(&event.player.gameprofile.name),
-                ChatColorStyle::CustomGradient(gradient_name) => apply_custom_gradient(&event.player.gameprofile.name, gradient_name).await,
-            },
-            None => parse_color_codes(&event.player.gameprofile.name),
-        };
+        // Si aucune couleur ni aucun tag n'est défini et que le chat n'est pas restreint,
+        // ne rien faire (laisser le chat vanilla)
+        if !local_chat.enabled && !has_chat_style(&event.player).await {
+            return;
+        }
+
+        let chat_line = build_chat_line(&event.player, &message, None).await;
+        let plain_line = plain_chat_line(&event.player, &message);
 
         // Cancel the original event
         event.set_cancelled(true);
 
-        // Broadcast the formatted message à tous les joueurs du monde
-        let world = event.player.living_entity.entity.world.read().await;
-        world
-            .broadcast_message(
-                &formatted_message,
-                &formatted_name,
-                0, // Chat type
-                None,
-            )
-            .await;
+        // Respecte les destinataires filtrés par les autres plugins (ignore, vanish...), tels quels :
+        // une liste vidée par un plugin ne part à personne (voir ChatRecipientsHandler)
+        let mut recipients = filter_scope(&event.player, event.recipients.clone(), chat_scope).await;
+
+        if local {
+            recipients = filter_in_range(&event.player, recipients, local_chat.radius).await;
+        }
+        let nobody_heard = local && recipients.iter().all(|p| p.gameprofile.id == event.player.gameprofile.id);
+
+        // Rendu par destinataire : accessibilité et couleurs des autres désactivées
+        for player in recipients {
+            deliver_chat_line(&player, &event.player, &chat_line, &plain_line).await;
+        }
+
+        // L'expéditeur seul à portée : on le prévient que personne ne l'a entendu
+        if nobody_heard {
+            event.player.send_system_message(&parse_color_codes(&format!(
+                "&7Nobody heard you. Start your message with {} or use /shout to talk to everyone.",
+                local_chat.shout_prefix
+            ))).await;
+        }
 
         // Log le message final envoyé
-        log::info!(
-            "<chat> {}: {}",
+        log::debug!(
+            "<chat{}> {}: {}",
+            if local { ":local" } else { "" },
             event.player.gameprofile.name,
-            formatted_message.clone().get_text()
+            chat_line.get_text()
         );
     }
-} 
\ No newline at end of file
+}
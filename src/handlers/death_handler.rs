@@ -0,0 +1,31 @@
+use std::sync::Arc;
+use pumpkin::plugin::{
+    player::player_death::PlayerDeathEvent,
+    Cancellable, EventHandler,
+};
+use crate::config::DeathMessageMode;
+use crate::storage::PLUGIN_CONFIG;
+use crate::utils::{broadcast_to_viewers, restyle_player_names, styled_player_name};
+
+pub struct DeathEventHandler;
+
+#[async_trait::async_trait]
+impl EventHandler<PlayerDeathEvent> for DeathEventHandler {
+    async fn handle_blocking(&self, server: &Arc<pumpkin::server::Server>, event: &mut PlayerDeathEvent) {
+        let mode = PLUGIN_CONFIG.lock().await.messages.death;
+        match mode {
+            DeathMessageMode::Vanilla => {}
+            DeathMessageMode::Hide => event.set_cancelled(true),
+            DeathMessageMode::Restyle => {
+                // La victime et un éventuel tueur joueur apparaissent dans les arguments du message traduit
+                let mut names = Vec::new();
+                for player in server.get_all_players().await {
+                    names.push((player.gameprofile.name.clone(), styled_player_name(&player).await));
+                }
+                // Envoyé par joueur plutôt que par le serveur, pour appliquer le mode d'accessibilité de chacun
+                event.set_cancelled(true);
+                broadcast_to_viewers(server, &restyle_player_names(event.death_message.clone(), &names)).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use pumpkin::plugin::{
+    player::{player_edit_book::PlayerEditBookEvent, player_item_rename::PlayerItemRenameEvent},
+    EventHandler,
+};
+use crate::utils::{filter_codes_with, color_codes_to_json, ITEM_PERMISSION};
+
+pub struct ItemRenameHandler;
+
+#[async_trait::async_trait]
+impl EventHandler<PlayerItemRenameEvent> for ItemRenameHandler {
+    async fn handle_blocking(&self, _server: &Arc<pumpkin::server::Server>, event: &mut PlayerItemRenameEvent) {
+        if !event.new_name.contains('&') {
+            return;
+        }
+        // Même filtrage que le chat : sans permission les codes sont retirés, les codes bloqués le sont toujours.
+        // Le nom (comme les pages et le titre des livres) devient un composant stylé en texte JSON
+        let name = filter_codes_with(&event.player, &event.new_name, ITEM_PERMISSION).await;
+        event.new_name = color_codes_to_json(&name);
+    }
+}
+
+pub struct BookEditHandler;
+
+#[async_trait::async_trait]
+impl EventHandler<PlayerEditBookEvent> for BookEditHandler {
+    async fn handle_blocking(&self, _server: &Arc<pumpkin::server::Server>, event: &mut PlayerEditBookEvent) {
+        for page in event.pages.iter_mut() {
+            if page.contains('&') {
+                let filtered = filter_codes_with(&event.player, page, ITEM_PERMISSION).await;
+                *page = color_codes_to_json(&filtered);
+            }
+        }
+        // Titre du livre signé
+        if let Some(title) = event.title.as_mut() {
+            if title.contains('&') {
+                let filtered = filter_codes_with(&event.player, title, ITEM_PERMISSION).await;
+                *title = color_codes_to_json(&filtered);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::Path;
+use std::sync::Arc;
+use pumpkin::entity::player::Player;
+use pumpkin::plugin::{
+    player::{player_join::PlayerJoinEvent, player_leave::PlayerLeaveEvent},
+    Cancellable, EventHandler,
+};
+use pumpkin_util::text::TextComponent;
+use uuid::Uuid;
+use crate::storage::{KNOWN_PLAYERS, PLAYER_NAMES, PLUGIN_CONFIG};
+use crate::utils::{
+    apply_name_team, broadcast_to_viewers, expire_player_grant, remove_name_team, revert_out_of_season_styles, render_template, resolve_placeholders, save_data,
+    send_tab_names_to, styled_player_name, update_tab_name,
+};
+
+/// Rend un template de message de connexion / déconnexion ({name} = pseudo coloré)
+async fn render_player_message(template: &str, player: &Player) -> TextComponent {
+    let values = resolve_placeholders(template, player).await;
+    render_template(template, &[("name", styled_player_name(player).await)], &values)
+}
+
+/// Le serveur a déjà des données pour ce joueur (world/playerdata/<uuid>.dat) : il a joué avant,
+/// même si le plugin ne l'a jamais vu (plugin installé ou mis à jour sur un serveur existant)
+fn has_server_player_data(uuid: &Uuid) -> bool {
+    Path::new("world").join("playerdata").join(format!("{uuid}.dat")).exists()
+}
+
+pub struct JoinEventHandler;
+
+#[async_trait::async_trait]
+impl EventHandler<PlayerJoinEvent> for JoinEventHandler {
+    async fn handle_blocking(&self, server: &Arc<pumpkin::server::Server>, event: &mut PlayerJoinEvent) {
+        // Couleur temporaire expirée pendant que le joueur était hors ligne
+        expire_player_grant(&event.player).await;
+        revert_out_of_season_styles(server).await;
+
+        // Applique la couleur du pseudo au nametag dès la connexion
+        apply_name_team(server, &event.player).await;
+        // Préfixe / suffixe et couleur du pseudo dans la liste des joueurs
+        update_tab_name(server, &event.player).await;
+        send_tab_names_to(server, &event.player).await;
+
+        let uuid = event.player.gameprofile.id;
+        let newly_known = KNOWN_PLAYERS.lock().await.insert(uuid);
+        let first_join = newly_known && !has_server_player_data(&uuid);
+        // Pseudo retenu pour les commandes d'admin visant ce joueur hors ligne
+        let name = event.player.gameprofile.name.clone();
+        let renamed = PLAYER_NAMES.lock().await.insert(uuid, name.clone()) != Some(name);
+        if newly_known || renamed {
+            if let Err(e) = save_data().await {
+                log::error!("[ChatColor] Failed to save player data: {}", e);
+            }
+        }
+
+        let template = {
+            let config = PLUGIN_CONFIG.lock().await;
+            let first_join_template = if first_join { config.messages.first_join.clone() } else { None };
+            first_join_template.or_else(|| config.messages.join.clone())
+        };
+        match template {
+            // Template vide : pas de message de connexion
+            Some(template) if template.is_empty() => event.set_cancelled(true),
+            // Le serveur enverrait un seul composant à tous : on l'envoie nous-mêmes à chaque joueur (accessibilité)
+            Some(template) => {
+                event.set_cancelled(true);
+                broadcast_to_viewers(server, &render_player_message(&template, &event.player).await).await;
+            }
+            None => {}
+        }
+    }
+}
+
+pub struct LeaveEventHandler;
+
+#[async_trait::async_trait]
+impl EventHandler<PlayerLeaveEvent> for LeaveEventHandler {
+    async fn handle_blocking(&self, server: &Arc<pumpkin::server::Server>, event: &mut PlayerLeaveEvent) {
+        // Nettoie l'équipe du joueur pour ne pas accumuler d'équipes orphelines
+        remove_name_team(server, &event.player).await;
+
+        let template = PLUGIN_CONFIG.lock().await.messages.quit.clone();
+        match template {
+            // Template vide : pas de message de déconnexion
+            Some(template) if template.is_empty() => event.set_cancelled(true),
+            Some(template) => {
+                event.set_cancelled(true);
+                broadcast_to_viewers(server, &render_player_message(&template, &event.player).await).await;
+            }
+            None => {}
+        }
+    }
+}
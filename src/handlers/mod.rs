@@ -1,3 +1,11 @@
 pub mod chat_handler;
+pub mod join_handler;
+pub mod death_handler;
+pub mod sign_handler;
+pub mod item_handler;
 
-pub use chat_handler::*; 
\ No newline at end of file
+pub use chat_handler::*;
+pub use join_handler::*;
+pub use death_handler::*;
+pub use sign_handler::*;
+pub use item_handler::*;
\ No newline at end of file
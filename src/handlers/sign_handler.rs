@@ -0,0 +1,25 @@
+use std::sync::Arc;
+use pumpkin::plugin::{
+    block::sign_change::SignChangeEvent,
+    EventHandler,
+};
+use crate::utils::{filter_blocked_codes, color_codes_to_json, SIGN_PERMISSION};
+
+pub struct SignChangeHandler;
+
+#[async_trait::async_trait]
+impl EventHandler<SignChangeEvent> for SignChangeHandler {
+    async fn handle_blocking(&self, _server: &Arc<pumpkin::server::Server>, event: &mut SignChangeEvent) {
+        // Sans la permission, les & restent du texte normal
+        if !event.player.has_permission(SIGN_PERMISSION).await {
+            return;
+        }
+
+        // Chaque ligne devient un composant stylé (texte JSON), comme le texte des panneaux vanilla
+        for line in event.lines.iter_mut() {
+            if line.contains('&') {
+                *line = color_codes_to_json(&filter_blocked_codes(&event.player, line).await);
+            }
+        }
+    }
+}
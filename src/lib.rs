@@ -25,23 +25,194 @@ async fn register_permissions(context: &Context) -> Result<(), String> {
     let chatcolor_perm = Permission::new(
         "chat-color:command.chatcolor",
         "Use the /chatcolor command",
-        PermissionDefault::Op(PermissionLvl::One),
+        PermissionDefault::Allow, // chaque entrée est réservée par sa permission ou un déblocage
     );
     context.register_permission(chatcolor_perm).await?;
 
     let namecolor_perm = Permission::new(
         "chat-color:command.namecolor",
         "Use the /namecolor command",
-        PermissionDefault::Op(PermissionLvl::One),
+        PermissionDefault::Allow,
     );
     context.register_permission(namecolor_perm).await?;
+
+    let chattag_perm = Permission::new(
+        "chat-color:command.chattag",
+        "Use the /chattag command",
+        PermissionDefault::Op(PermissionLvl::Two),
+    );
+    context.register_permission(chattag_perm).await?;
+
+    let shout_perm = Permission::new(
+        "chat-color:command.shout",
+        "Use the /shout command",
+        PermissionDefault::Allow,
+    );
+    context.register_permission(shout_perm).await?;
+
+    let channel_perm = Permission::new(
+        "chat-color:command.channel",
+        "Use the /ch command",
+        PermissionDefault::Allow,
+    );
+    context.register_permission(channel_perm).await?;
+
+    let msg_perm = Permission::new(
+        "chat-color:command.msg",
+        "Use the /msg, /tell and /r commands",
+        PermissionDefault::Allow,
+    );
+    context.register_permission(msg_perm).await?;
+
+    let me_perm = Permission::new(
+        "chat-color:command.me",
+        "Use the /me command",
+        PermissionDefault::Allow,
+    );
+    context.register_permission(me_perm).await?;
+
+    let say_perm = Permission::new(
+        "chat-color:command.say",
+        "Use the /say command",
+        PermissionDefault::Op(PermissionLvl::Two),
+    );
+    context.register_permission(say_perm).await?;
+
+    // Codes & dans le texte écrit par les joueurs (chat, /me, /msg...)
+    let codes_perm = Permission::new(
+        crate::utils::CODES_PERMISSION,
+        "Use & color codes in chat",
+        PermissionDefault::Allow,
+    );
+    context.register_permission(codes_perm).await?;
+
+    let sign_perm = Permission::new(
+        crate::utils::SIGN_PERMISSION,
+        "Use & color codes on signs",
+        PermissionDefault::Op(PermissionLvl::Two),
+    );
+    context.register_permission(sign_perm).await?;
+
+    let item_perm = Permission::new(
+        crate::utils::ITEM_PERMISSION,
+        "Use & color codes in anvil item names and books",
+        PermissionDefault::Op(PermissionLvl::Two),
+    );
+    context.register_permission(item_perm).await?;
+
+    let custom_perm = Permission::new(
+        crate::commands::chatcolor::CUSTOM_GRADIENT_PERMISSION,
+        "Create a personal gradient with /chatcolor custom",
+        PermissionDefault::Op(PermissionLvl::One),
+    );
+    context.register_permission(custom_perm).await?;
+
+    let blocked_codes_perm = Permission::new(
+        crate::utils::BLOCKED_CODES_BYPASS_PERMISSION,
+        "Use the & codes blocked in the config (ex: &k)",
+        PermissionDefault::Op(PermissionLvl::Four),
+    );
+    context.register_permission(blocked_codes_perm).await?;
+
+    // Permissions des préfixes / suffixes et des canaux définies dans la config
+    let (tag_nodes, channel_nodes): (Vec<String>, Vec<String>) = {
+        let config = crate::storage::PLUGIN_CONFIG.lock().await;
+        (
+            config.prefixes.values()
+                .chain(config.suffixes.values())
+                .filter_map(|tag| tag.permission.clone())
+                .collect(),
+            config.channels.values()
+                .filter_map(|channel| channel.permission.clone())
+                .collect(),
+        )
+    };
+    for node in tag_nodes {
+        let tag_perm = Permission::new(&node, "Display a chat tag", PermissionDefault::Deny);
+        if let Err(e) = context.register_permission(tag_perm).await {
+            log::warn!("[ChatColor] Could not register tag permission {}: {}", node, e);
+        }
+    }
+    // Une permission par entrée de la palette (OP par défaut, comme les commandes)
+    // Toutes les entrées, y compris cachées ou hors saison
+    let color_names = crate::utils::palette_names(&*crate::storage::PLUGIN_CONFIG.lock().await);
+    for name in color_names {
+        let node = crate::utils::color_permission(&name);
+        let color_perm = Permission::new(&node, "Use a palette color or gradient", PermissionDefault::Op(PermissionLvl::One));
+        if let Err(e) = context.register_permission(color_perm).await {
+            log::warn!("[ChatColor] Could not register color permission {}: {}", node, e);
+        }
+    }
+    // Une permission par preset (OP par défaut, comme les entrées de la palette)
+    let preset_names = crate::utils::preset_names(&*crate::storage::PLUGIN_CONFIG.lock().await);
+    for name in preset_names {
+        let node = crate::utils::preset_permission(&name);
+        let preset_perm = Permission::new(&node, "Apply a chat color preset", PermissionDefault::Op(PermissionLvl::One));
+        if let Err(e) = context.register_permission(preset_perm).await {
+            log::warn!("[ChatColor] Could not register preset permission {}: {}", node, e);
+        }
+    }
+    for node in channel_nodes {
+        let channel_perm = Permission::new(&node, "Use a chat channel", PermissionDefault::Op(PermissionLvl::Two));
+        if let Err(e) = context.register_permission(channel_perm).await {
+            log::warn!("[ChatColor] Could not register channel permission {}: {}", node, e);
+        }
+    }
     Ok(())
 }
 
 async fn register_events(context: &Context) {
-    // Register the chat event handler
+    // Destinataires explicites avant tous les autres plugins de chat (priorité la plus haute, exécutée en premier)
+    context.register_event::<pumpkin::plugin::player::player_chat::PlayerChatEvent, ChatRecipientsHandler>(
+        Arc::new(ChatRecipientsHandler),
+        EventPriority::Highest,
+        true, // Blocking handler (remplit la liste des destinataires)
+    ).await;
+
+    // Register the chat event handler (priorité configurable pour cohabiter avec les autres plugins de chat)
+    let chat_priority = crate::storage::PLUGIN_CONFIG.lock().await.settings.chat_priority;
     context.register_event::<pumpkin::plugin::player::player_chat::PlayerChatEvent, ChatEventHandler>(
         Arc::new(ChatEventHandler),
+        chat_priority.to_event_priority(),
+        true, // Blocking handler
+    ).await;
+
+    // Connexion / déconnexion : nametags, liste des joueurs et messages
+    context.register_event::<pumpkin::plugin::player::player_join::PlayerJoinEvent, JoinEventHandler>(
+        Arc::new(JoinEventHandler),
+        EventPriority::Normal,
+        true, // Blocking handler (modifie le message de connexion)
+    ).await;
+
+    context.register_event::<pumpkin::plugin::player::player_leave::PlayerLeaveEvent, LeaveEventHandler>(
+        Arc::new(LeaveEventHandler),
+        EventPriority::Normal,
+        true, // Blocking handler (modifie le message de déconnexion)
+    ).await;
+
+    // Panneaux colorés
+    context.register_event::<pumpkin::plugin::block::sign_change::SignChangeEvent, SignChangeHandler>(
+        Arc::new(SignChangeHandler),
+        EventPriority::Normal,
+        true, // Blocking handler (modifie les lignes)
+    ).await;
+
+    // Noms d'objets (enclume) et livres colorés
+    context.register_event::<pumpkin::plugin::player::player_item_rename::PlayerItemRenameEvent, ItemRenameHandler>(
+        Arc::new(ItemRenameHandler),
+        EventPriority::Normal,
+        true, // Blocking handler (modifie le nom)
+    ).await;
+
+    context.register_event::<pumpkin::plugin::player::player_edit_book::PlayerEditBookEvent, BookEditHandler>(
+        Arc::new(BookEditHandler),
+        EventPriority::Normal,
+        true, // Blocking handler (modifie les pages)
+    ).await;
+
+    // Messages de mort
+    context.register_event::<pumpkin::plugin::player::player_death::PlayerDeathEvent, DeathEventHandler>(
+        Arc::new(DeathEventHandler),
         EventPriority::Normal,
         true, // Blocking handler
     ).await;
@@ -57,6 +228,48 @@ async fn register_events(context: &Context) {
         init_namecolor_command_tree(),
         "chat-color:command.namecolor",
     ).await;
+
+    // Enregistrer la commande /chattag
+    context.register_command(
+        init_chattag_command_tree(),
+        "chat-color:command.chattag",
+    ).await;
+
+    // Enregistrer la commande /shout (chat local)
+    context.register_command(
+        init_shout_command_tree(),
+        "chat-color:command.shout",
+    ).await;
+
+    // Enregistrer la commande /ch (canaux de chat)
+    context.register_command(
+        init_channel_command_tree(),
+        "chat-color:command.channel",
+    ).await;
+
+    // Messages privés colorés (remplacent /msg, /tell et /r du serveur)
+    if crate::storage::PLUGIN_CONFIG.lock().await.private_messages.enabled {
+        context.register_command(
+            init_msg_command_tree(),
+            "chat-color:command.msg",
+        ).await;
+        context.register_command(
+            init_reply_command_tree(),
+            "chat-color:command.msg",
+        ).await;
+    }
+
+    // /me et /say colorés (remplacent les commandes du serveur)
+    if crate::storage::PLUGIN_CONFIG.lock().await.emotes.enabled {
+        context.register_command(
+            init_me_command_tree(),
+            "chat-color:command.me",
+        ).await;
+        context.register_command(
+            init_say_command_tree(),
+            "chat-color:command.say",
+        ).await;
+    }
 }
 
 #[plugin_method]
@@ -73,6 +286,19 @@ async fn on_load(&mut self, context: &Context) -> Result<(), String> {
         log::error!("[ChatColor] Failed to load data: {}", e);
     }
 
+    *crate::storage::SERVER.lock().await = Arc::downgrade(&context.server);
+
+    // Vérification périodique des couleurs temporaires et saisonnières
+    let server = context.server.clone();
+    self.expiry_task = Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(GRANT_CHECK_INTERVAL));
+        loop {
+            interval.tick().await;
+            expire_grants(&server).await;
+            revert_out_of_season_styles(&server).await;
+        }
+    }));
+
     register_permissions(context).await?;
     register_events(context).await;
 
@@ -82,6 +308,11 @@ async fn on_load(&mut self, context: &Context) -> Result<(), String> {
 
 #[plugin_method]
 async fn on_unload(&mut self, _context: &Context) -> Result<(), String> {
+    // Arrête la vérification périodique (elle garde une référence au serveur)
+    if let Some(task) = self.expiry_task.take() {
+        task.abort();
+    }
+
     // Sauvegarder les données avant de décharger le plugin
     if let Err(e) = save_data().await {
         log::error!("[ChatColor] Failed to save data: {}", e);
@@ -92,11 +323,14 @@ async fn on_unload(&mut self, _context: &Context) -> Result<(), String> {
 }
 
 #[plugin_impl]
-pub struct Plugin {}
+pub struct Plugin {
+    // Tâche de vérification des couleurs temporaires et saisonnières, arrêtée au déchargement
+    expiry_task: Option<tokio::task::JoinHandle<()>>,
+}
 
 impl Plugin {
     pub fn new() -> Self {
-        Plugin {}
+        Plugin { expiry_task: None }
     }
 }
 
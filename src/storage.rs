@@ -1,7 +1,9 @@
-use crate::config::{ChatColorStyle, PluginConfig, Settings};
+use crate::config::{AccessibilityMode, ChatColorStyle, ColorGrant, EmoteConfig, LocalChatConfig, MessagesConfig, PluginConfig, PrivateMessageConfig, ReadabilityConfig, Settings};
 use once_cell::sync::Lazy;
+use pumpkin::server::Server;
+use std::sync::Weak;
 use tokio::sync::Mutex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 // Stockage global de la couleur/style par défaut de chaque joueur
@@ -10,14 +12,79 @@ pub static PLAYER_COLORS: Lazy<Mutex<HashMap<Uuid, ChatColorStyle>>> = Lazy::new
 // Stockage global de la couleur/style du pseudo de chaque joueur
 pub static PLAYER_NAME_COLORS: Lazy<Mutex<HashMap<Uuid, ChatColorStyle>>> = Lazy::new(|| Mutex::new(HashMap::new()));
 
+// Clé du préfixe attribué manuellement à chaque joueur (prioritaire sur les permissions)
+pub static PLAYER_PREFIXES: Lazy<Mutex<HashMap<Uuid, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Clé du suffixe attribué manuellement à chaque joueur
+pub static PLAYER_SUFFIXES: Lazy<Mutex<HashMap<Uuid, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Canaux de chat rejoints par chaque joueur
+pub static PLAYER_CHANNELS: Lazy<Mutex<HashMap<Uuid, HashSet<String>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Canal sur lequel chaque joueur écrit (absent = chat global)
+pub static PLAYER_FOCUSED_CHANNEL: Lazy<Mutex<HashMap<Uuid, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Joueurs dont le message en cours a été envoyé avec /shout (chat global même si le chat local est actif)
+pub static SHOUTING: Lazy<Mutex<HashSet<Uuid>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+// Dernier interlocuteur en message privé de chaque joueur (pour /r)
+pub static LAST_CONVERSATION: Lazy<Mutex<HashMap<Uuid, Uuid>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Mode d'accessibilité de chaque joueur (absent = couleurs normales)
+pub static PLAYER_ACCESSIBILITY: Lazy<Mutex<HashMap<Uuid, AccessibilityMode>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Joueurs qui voient les messages des autres sans couleurs ni tags (/chatcolor toggle others)
+pub static HIDE_OTHER_COLORS: Lazy<Mutex<HashSet<Uuid>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+// Couleur de chat temporaire de chaque joueur (/chatcolor grant)
+pub static PLAYER_GRANTS: Lazy<Mutex<HashMap<Uuid, ColorGrant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Entrées de la palette débloquées par chaque joueur (récompenses), en plus de ses permissions
+pub static PLAYER_UNLOCKS: Lazy<Mutex<HashMap<Uuid, HashSet<String>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Mise en forme du pseudo de chaque joueur, posée par un preset (codes de format, ex: "l")
+pub static PLAYER_NAME_FORMATS: Lazy<Mutex<HashMap<Uuid, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Mise en forme des messages de chaque joueur, posée par un preset
+pub static PLAYER_MESSAGE_FORMATS: Lazy<Mutex<HashMap<Uuid, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Clé du préfixe posé par un preset (après celui d'un admin, avant ceux des permissions)
+pub static PLAYER_PRESET_PREFIXES: Lazy<Mutex<HashMap<Uuid, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Joueurs déjà venus sur le serveur (pour le message de première connexion)
+pub static KNOWN_PLAYERS: Lazy<Mutex<HashSet<Uuid>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+// Dernier pseudo connu de chaque joueur (commandes d'admin sur des joueurs hors ligne)
+pub static PLAYER_NAMES: Lazy<Mutex<HashMap<Uuid, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Serveur, conservé au chargement pour les actions hors commande (clics dans le coffre du sélecteur).
+// Référence faible : le plugin ne garde pas le serveur en vie et la valeur est remplacée à chaque chargement
+pub static SERVER: Lazy<Mutex<Weak<Server>>> = Lazy::new(|| Mutex::new(Weak::new()));
+
 // Configuration du plugin
 pub static PLUGIN_CONFIG: Lazy<Mutex<PluginConfig>> = Lazy::new(|| Mutex::new(PluginConfig {
     save_interval: 300, // 5 minutes par défaut
     auto_save: true,
     simple_colors: HashMap::new(),
     gradients: HashMap::new(),
+    local_chat: LocalChatConfig::default(),
+    channels: HashMap::new(),
+    private_messages: PrivateMessageConfig::default(),
+    emotes: EmoteConfig::default(),
+    messages: MessagesConfig::default(),
+    readability: ReadabilityConfig::default(),
+    prefixes: HashMap::new(),
+    suffixes: HashMap::new(),
+    presets: HashMap::new(),
     settings: Settings {
         default_chat_color: "white".to_string(),
         default_name_color: "white".to_string(),
+        nametag_colors: true,
+        chat_format: crate::utils::DEFAULT_CHAT_FORMAT.to_string(),
+        chat_scope: crate::config::ChatScope::Server,
+        chat_priority: crate::config::HandlerPriority::Normal,
+        blocked_codes: vec!['k'],
+        max_custom_stops: 5,
+        keep_seasonal_styles: true,
     },
 })); 
\ No newline at end of file
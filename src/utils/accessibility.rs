@@ -0,0 +1,136 @@
+use pumpkin::entity::player::Player;
+use pumpkin::server::Server;
+use pumpkin_util::text::color::{Color, RGBColor};
+use pumpkin_util::text::TextComponent;
+use crate::config::AccessibilityMode;
+use crate::storage::{PLAYER_ACCESSIBILITY, PLUGIN_CONFIG};
+use crate::utils::{lighten_to_contrast, named_color_rgb, walk_component_mut};
+
+// Matrices de simulation des daltonismes (Machado et al., sévérité maximale)
+const PROTANOPIA: [[f32; 3]; 3] = [
+    [0.152_286, 1.052_583, -0.204_868],
+    [0.114_503, 0.786_281, 0.099_216],
+    [-0.003_882, -0.048_116, 1.051_998],
+];
+const DEUTERANOPIA: [[f32; 3]; 3] = [
+    [0.367_322, 0.860_646, -0.227_968],
+    [0.280_085, 0.672_501, 0.047_413],
+    [-0.011_820, 0.042_940, 0.968_881],
+];
+const TRITANOPIA: [[f32; 3]; 3] = [
+    [1.255_528, -0.076_749, -0.178_779],
+    [-0.078_411, 0.930_809, 0.147_602],
+    [0.004_733, 0.691_367, 0.303_900],
+];
+
+// Report de l'erreur sur les canaux que le joueur distingue (ligne = canal corrigé, colonne = erreur R, G, B)
+// Protanopie / deutéranopie : la différence rouge-vert est reportée sur le vert et le bleu
+const RED_GREEN_CORRECTION: [[f32; 3]; 3] = [
+    [0.0, 0.0, 0.0],
+    [0.7, 1.0, 0.0],
+    [0.7, 0.0, 1.0],
+];
+// Tritanopie : la différence bleu-jaune est reportée sur le rouge et le vert
+const BLUE_YELLOW_CORRECTION: [[f32; 3]; 3] = [
+    [1.0, 0.0, 0.7],
+    [0.0, 1.0, 0.7],
+    [0.0, 0.0, 0.0],
+];
+
+/// Contraste visé par le mode contraste élevé
+const HIGH_CONTRAST_RATIO: f64 = 7.0;
+
+/// Daltonisation : l'information de couleur perdue par le joueur est reportée sur les canaux qu'il distingue
+fn daltonize(rgb: (u8, u8, u8), simulation: &[[f32; 3]; 3], correction: &[[f32; 3]; 3]) -> (u8, u8, u8) {
+    let original = [rgb.0 as f32, rgb.1 as f32, rgb.2 as f32];
+    let simulated: Vec<f32> = simulation.iter()
+        .map(|row| row[0] * original[0] + row[1] * original[1] + row[2] * original[2])
+        .collect();
+    let error = [original[0] - simulated[0], original[1] - simulated[1], original[2] - simulated[2]];
+    let shifted: Vec<f32> = correction.iter()
+        .zip(original)
+        .map(|(row, channel)| channel + row[0] * error[0] + row[1] * error[1] + row[2] * error[2])
+        .collect();
+    let clamp = |c: f32| c.round().clamp(0.0, 255.0) as u8;
+    (clamp(shifted[0]), clamp(shifted[1]), clamp(shifted[2]))
+}
+
+/// Couleur vue par un joueur avec ce mode (`None` = pas de couleur).
+/// `background` est le fond du chat (section `readability` de la config).
+pub fn transform_rgb(mode: AccessibilityMode, rgb: (u8, u8, u8), background: (u8, u8, u8)) -> Option<(u8, u8, u8)> {
+    match mode {
+        AccessibilityMode::Protanopia => Some(daltonize(rgb, &PROTANOPIA, &RED_GREEN_CORRECTION)),
+        AccessibilityMode::Deuteranopia => Some(daltonize(rgb, &DEUTERANOPIA, &RED_GREEN_CORRECTION)),
+        AccessibilityMode::Tritanopia => Some(daltonize(rgb, &TRITANOPIA, &BLUE_YELLOW_CORRECTION)),
+        AccessibilityMode::HighContrast => Some(lighten_to_contrast(rgb, background, HIGH_CONTRAST_RATIO)),
+        AccessibilityMode::NoColors => None,
+    }
+}
+
+/// Applique le mode à chaque couleur (nommée ou RGB) de l'arbre du composant
+pub fn apply_accessibility(component: &TextComponent, mode: AccessibilityMode, background: (u8, u8, u8)) -> TextComponent {
+    let mut base = component.0.clone();
+    walk_component_mut(&mut base, &mut |node| {
+        let rgb = match &node.style.color {
+            Some(Color::Named(color)) => named_color_rgb(*color),
+            Some(Color::Rgb(color)) => (color.red, color.green, color.blue),
+            _ => return,
+        };
+        node.style.color = transform_rgb(mode, rgb, background).map(|(r, g, b)| Color::Rgb(RGBColor::new(r, g, b)));
+    });
+    TextComponent(base)
+}
+
+/// Envoie un message à un joueur en tenant compte de son mode d'accessibilité
+pub async fn send_to_viewer(viewer: &Player, component: &TextComponent) {
+    let mode = PLAYER_ACCESSIBILITY.lock().await.get(&viewer.gameprofile.id).copied();
+    match mode {
+        Some(mode) => {
+            let background = PLUGIN_CONFIG.lock().await.readability.background_rgb();
+            viewer.send_system_message(&apply_accessibility(component, mode, background)).await
+        }
+        None => viewer.send_system_message(component).await,
+    }
+}
+
+/// Envoie un message à tous les joueurs connectés, chacun avec son mode d'accessibilité
+/// (messages de connexion, déconnexion et mort, que le serveur enverrait en un seul composant)
+pub async fn broadcast_to_viewers(server: &Server, component: &TextComponent) {
+    for viewer in server.get_all_players().await {
+        send_to_viewer(&viewer, component).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daltonize_keeps_grays() {
+        for mode in [AccessibilityMode::Protanopia, AccessibilityMode::Deuteranopia, AccessibilityMode::Tritanopia] {
+            let gray = transform_rgb(mode, (128, 128, 128), (0, 0, 0)).unwrap();
+            assert!((gray.0 as i32 - 128).abs() <= 2 && (gray.1 as i32 - 128).abs() <= 2 && (gray.2 as i32 - 128).abs() <= 2);
+        }
+    }
+
+    #[test]
+    fn test_no_colors() {
+        assert_eq!(transform_rgb(AccessibilityMode::NoColors, (255, 0, 0), (0, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_tritanopia_moves_blue_to_red_green() {
+        // Le bleu pur est reporté sur le rouge et le vert, le canal bleu reste intact
+        let (r, g, b) = transform_rgb(AccessibilityMode::Tritanopia, (0, 0, 255), (0, 0, 0)).unwrap();
+        assert_eq!(b, 255);
+        assert!(r > 100 && g > 50);
+    }
+
+    #[test]
+    fn test_high_contrast_uses_background() {
+        // Sur fond blanc, le contraste élevé ne doit pas éclaircir vers le blanc
+        let white = (255, 255, 255);
+        let dark_blue = transform_rgb(AccessibilityMode::HighContrast, (0, 0, 170), white).unwrap();
+        assert_eq!(dark_blue, (0, 0, 170));
+    }
+}
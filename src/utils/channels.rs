@@ -0,0 +1,46 @@
+use std::sync::Arc;
+use pumpkin::entity::player::Player;
+use pumpkin::server::Server;
+use crate::config::ChannelConfig;
+use crate::storage::{PLAYER_CHANNELS, PLAYER_FOCUSED_CHANNEL, PLUGIN_CONFIG};
+
+/// Nom réservé du chat normal : `/ch focus global` revient au chat public
+pub const GLOBAL_CHANNEL: &str = "global";
+
+/// Retourne la configuration d'un canal par son nom
+pub async fn get_channel_config(name: &str) -> Option<ChannelConfig> {
+    let config = PLUGIN_CONFIG.lock().await;
+    config.channels.get(&name.to_lowercase()).cloned()
+}
+
+/// Vérifie que le joueur a la permission du canal (si le canal en demande une)
+pub async fn can_use_channel(player: &Player, channel: &ChannelConfig) -> bool {
+    match &channel.permission {
+        Some(node) => player.has_permission(node).await,
+        None => true,
+    }
+}
+
+/// Canal sur lequel le joueur écrit actuellement, s'il existe toujours et qu'il y a accès
+pub async fn focused_channel(player: &Player) -> Option<(String, ChannelConfig)> {
+    let name = PLAYER_FOCUSED_CHANNEL.lock().await.get(&player.gameprofile.id).cloned()?;
+    let channel = get_channel_config(&name).await?;
+    if !can_use_channel(player, &channel).await {
+        return None;
+    }
+    Some((name, channel))
+}
+
+/// Joueurs en ligne membres du canal et ayant encore sa permission
+pub async fn channel_members(server: &Server, name: &str, channel: &ChannelConfig) -> Vec<Arc<Player>> {
+    let mut members = Vec::new();
+    for player in server.get_all_players().await {
+        let joined = PLAYER_CHANNELS.lock().await
+            .get(&player.gameprofile.id)
+            .is_some_and(|channels| channels.contains(name));
+        if joined && can_use_channel(&player, channel).await {
+            members.push(player);
+        }
+    }
+    members
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use pumpkin_util::text::TextComponent;
+use crate::utils::{parse_color_codes, strip_color_codes};
+
+/// Format par défaut : reproduit l'affichage vanilla `<pseudo> message`
+pub const DEFAULT_CHAT_FORMAT: &str = "<{prefix}{name}{suffix}&r> {message}";
+
+/// Rend un template de format (ex: `"{name}&7 » {message}"`) en un seul TextComponent.
+///
+/// Le texte du template passe par `parse_color_codes`, chaque placeholder `{clé}` est
+/// remplacé par le composant fourni tel quel (il garde donc son propre style).
+/// Les valeurs texte (placeholders du registre) sont insérées en texte brut, sans leurs codes &,
+/// les composants sont prioritaires sur les valeurs. Un placeholder inconnu est laissé tel quel.
+/// Tout est fait en une seule passe : rien de ce qui est inséré n'est ré-analysé.
+pub fn render_template(
+    template: &str,
+    placeholders: &[(&str, TextComponent)],
+    values: &HashMap<String, String>,
+) -> TextComponent {
+    let mut result = TextComponent::text("");
+    let mut literal = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        literal.push_str(&rest[..start]);
+        // `{{` : accolade littérale
+        if rest[start + 1..].starts_with('{') {
+            literal.push('{');
+            rest = &rest[start + 2..];
+            continue;
+        }
+        let Some(len) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let key = &rest[start + 1..start + len];
+
+        if let Some((_, component)) = placeholders.iter().find(|(name, _)| *name == key) {
+            if !literal.is_empty() {
+                result = result.add_child(parse_color_codes(&literal));
+                literal.clear();
+            }
+            result = result.add_child(component.clone());
+        } else if let Some(value) = values.get(key) {
+            // Valeur fournie par un placeholder (parfois d'un autre plugin) : texte brut,
+            // ses codes & sont retirés pour qu'elle ne puisse pas changer le style de la ligne
+            if !literal.is_empty() {
+                result = result.add_child(parse_color_codes(&literal));
+                literal.clear();
+            }
+            result = result.add_child(TextComponent::text(strip_color_codes(value)));
+        } else {
+            literal.push_str(&rest[start..start + len + 1]);
+        }
+        rest = &rest[start + len + 1..];
+    }
+
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        result = result.add_child(parse_color_codes(&literal));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let result = render_template(
+            "&7[{name}&7] {message}",
+            &[
+                ("name", TextComponent::text("Steve")),
+                ("message", TextComponent::text("hello")),
+            ],
+            &HashMap::new(),
+        );
+        assert_eq!(result.get_text(), "[Steve] hello");
+    }
+
+    #[test]
+    fn test_render_template_unknown_placeholder() {
+        let result = render_template("{unknown} {name}", &[("name", TextComponent::text("Alex"))], &HashMap::new());
+        assert_eq!(result.get_text(), "{unknown} Alex");
+    }
+
+    #[test]
+    fn test_render_template_values() {
+        let values = HashMap::from([("world".to_string(), "overworld".to_string())]);
+        let result = render_template(
+            "[{world}] {{name} {message}",
+            &[("message", TextComponent::text("{world}"))],
+            &values,
+        );
+        // Le message du joueur n'est pas interprété
+        assert_eq!(result.get_text(), "[overworld] {name} {world}");
+    }
+
+    #[test]
+    fn test_render_template_values_cannot_inject_codes() {
+        let values = HashMap::from([("balance".to_string(), "&k&c100$".to_string())]);
+        let result = render_template("&7Balance: {balance}", &[], &values);
+        assert_eq!(result.get_text(), "Balance: 100$");
+    }
+}
@@ -0,0 +1,106 @@
+use pumpkin::entity::player::Player;
+
+/// Permission nécessaire pour utiliser les codes & dans le texte écrit par un joueur
+pub const CODES_PERMISSION: &str = "chat-color:codes";
+
+/// Permission nécessaire pour utiliser les codes & sur les panneaux
+pub const SIGN_PERMISSION: &str = "chat-color:sign";
+
+/// Permission nécessaire pour utiliser les codes & dans les noms d'objets (enclume) et les livres
+pub const ITEM_PERMISSION: &str = "chat-color:item";
+
+/// Permission permettant d'utiliser les codes bloqués par la config (ex: &k)
+pub const BLOCKED_CODES_BYPASS_PERMISSION: &str = "chat-color:codes.blocked";
+
+// Caractères valides après un & (couleurs, formats et reset)
+fn is_code_char(ch: char) -> bool {
+    matches!(ch, '0'..='9' | 'a'..='f' | 'k'..='o' | 'r')
+}
+
+/// Retire les codes & valides d'un texte (les & isolés sont conservés)
+pub fn strip_color_codes(text: &str) -> String {
+    strip_codes_matching(text, |_| true)
+}
+
+/// Retire seulement les codes & listés (ex: `['k']` pour l'obfuscation)
+pub fn strip_blocked_codes(text: &str, blocked: &[char]) -> String {
+    strip_codes_matching(text, |code| blocked.contains(&code))
+}
+
+// Recommence jusqu'à ce que rien ne change : retirer `&c` de `&&cc` recolle un nouveau code `&c`
+fn strip_codes_matching(text: &str, should_strip: impl Fn(char) -> bool) -> String {
+    let mut result = strip_codes_once(text, &should_strip);
+    loop {
+        let stripped = strip_codes_once(&result, &should_strip);
+        if stripped.len() == result.len() {
+            return result;
+        }
+        result = stripped;
+    }
+}
+
+fn strip_codes_once(text: &str, should_strip: &impl Fn(char) -> bool) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '&' {
+            if let Some(&next) = chars.peek() {
+                if is_code_char(next) && should_strip(next) {
+                    chars.next();
+                    continue;
+                }
+            }
+        }
+        result.push(ch);
+    }
+    result
+}
+
+/// Convertit les codes & en composant stylé, sérialisé en texte JSON (lignes de panneau, noms d'objets, livres)
+pub fn color_codes_to_json(text: &str) -> String {
+    serde_json::to_string(&crate::utils::parse_color_codes(text)).unwrap_or_else(|_| strip_color_codes(text))
+}
+
+/// Filtre les codes & du texte d'un joueur pour un usage protégé par `permission` :
+/// sans la permission tous les codes sont retirés, avec elle seuls les codes bloqués par la config le sont.
+pub async fn filter_codes_with(player: &Player, text: &str, permission: &str) -> String {
+    if !player.has_permission(permission).await {
+        return strip_color_codes(text);
+    }
+    filter_blocked_codes(player, text).await
+}
+
+/// Retire les codes bloqués par la config, sauf pour les joueurs ayant la permission de contournement
+pub async fn filter_blocked_codes(player: &Player, text: &str) -> String {
+    let blocked = crate::storage::PLUGIN_CONFIG.lock().await.settings.blocked_codes.clone();
+    if blocked.is_empty() || player.has_permission(BLOCKED_CODES_BYPASS_PERMISSION).await {
+        return text.to_string();
+    }
+    strip_blocked_codes(text, &blocked)
+}
+
+/// Filtre les codes & du texte de chat d'un joueur (chat, /me, /msg...)
+pub async fn filter_player_codes(player: &Player, text: &str) -> String {
+    filter_codes_with(player, text, CODES_PERMISSION).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_color_codes() {
+        assert_eq!(strip_color_codes("&aHello &lWorld&r!"), "Hello World!");
+        assert_eq!(strip_color_codes("Tom & Jerry &x"), "Tom & Jerry &x");
+        assert_eq!(strip_color_codes("&&cchello"), "hello");
+        assert_eq!(strip_color_codes("&&&ccchello"), "hello");
+    }
+
+    #[test]
+    fn test_strip_blocked_codes() {
+        assert_eq!(strip_blocked_codes("&k&cSecret &lbold", &['k']), "&cSecret &lbold");
+        assert_eq!(strip_blocked_codes("&aok", &[]), "&aok");
+        assert_eq!(strip_blocked_codes("&&kksecret", &['k']), "secret");
+        assert_eq!(strip_blocked_codes("&&kcsecret", &['k']), "&csecret");
+    }
+}
@@ -0,0 +1,386 @@
+use std::any::Any;
+use std::sync::Arc;
+use async_trait::async_trait;
+use pumpkin::command::CommandSender;
+use pumpkin::entity::player::Player;
+use pumpkin_data::data_component::DataComponent;
+use pumpkin_data::data_component_impl::{CustomNameImpl, LoreImpl};
+use pumpkin_data::item::Item;
+use pumpkin_data::screen::WindowType;
+use pumpkin_inventory::player::player_inventory::PlayerInventory;
+use pumpkin_inventory::screen_handler::{InventoryPlayer, ScreenHandler, ScreenHandlerBehaviour, ScreenHandlerFactory};
+use pumpkin_inventory::slot::NormalSlot;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+use pumpkin_world::inventory::{Clearable, Inventory};
+use pumpkin_world::item::ItemStack;
+use tokio::sync::Mutex;
+use crate::config::ChatColorStyle;
+use crate::storage::{PLAYER_COLORS, PLAYER_NAME_COLORS, PLUGIN_CONFIG, SERVER};
+use crate::utils::{apply_style, can_use_color, color_permission, describe_entry, display_name, dominant_color, entry_meta, style_name};
+use crate::commands::chatcolor::{get_available_colors, resolve_style};
+
+/// Taille du coffre (6 lignes) : 5 lignes de palette, la dernière pour les onglets
+const GUI_SIZE: usize = 54;
+/// Nombre d'entrées de palette par page du coffre
+pub const GUI_PAGE_SIZE: usize = 45;
+const SLOT_CHAT_TAB: usize = 45;
+const SLOT_PREVIOUS: usize = 48;
+const SLOT_NEXT: usize = 50;
+const SLOT_NAME_TAB: usize = 53;
+
+/// Onglet du sélecteur : couleur du chat ou couleur du pseudo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorTab {
+    Chat,
+    Name,
+}
+
+impl ColorTab {
+    /// Commande exécutée quand le joueur choisit une entrée
+    pub fn command(&self) -> &'static str {
+        match self {
+            ColorTab::Chat => "chatcolor",
+            ColorTab::Name => "namecolor",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ColorTab::Chat => "Chat colors",
+            ColorTab::Name => "Name colors",
+        }
+    }
+
+    async fn current_style(&self, player: &Player) -> Option<ChatColorStyle> {
+        match self {
+            ColorTab::Chat => PLAYER_COLORS.lock().await.get(&player.gameprofile.id).cloned(),
+            ColorTab::Name => PLAYER_NAME_COLORS.lock().await.get(&player.gameprofile.id).cloned(),
+        }
+    }
+}
+
+/// Laine de la couleur nommée la plus proche du style
+fn wool_for(color: NamedColor) -> &'static Item {
+    match color {
+        NamedColor::Black => &Item::BLACK_WOOL,
+        NamedColor::DarkBlue | NamedColor::Blue => &Item::BLUE_WOOL,
+        NamedColor::DarkGreen => &Item::GREEN_WOOL,
+        NamedColor::DarkAqua => &Item::CYAN_WOOL,
+        NamedColor::DarkRed | NamedColor::Red => &Item::RED_WOOL,
+        NamedColor::DarkPurple => &Item::PURPLE_WOOL,
+        NamedColor::Gold => &Item::ORANGE_WOOL,
+        NamedColor::Gray => &Item::LIGHT_GRAY_WOOL,
+        NamedColor::DarkGray => &Item::GRAY_WOOL,
+        NamedColor::Green => &Item::LIME_WOOL,
+        NamedColor::Aqua => &Item::LIGHT_BLUE_WOOL,
+        NamedColor::LightPurple => &Item::MAGENTA_WOOL,
+        NamedColor::Yellow => &Item::YELLOW_WOOL,
+        NamedColor::White => &Item::WHITE_WOOL,
+    }
+}
+
+/// Objet d'affichage avec un nom et une description (lore)
+fn display_item(item: &'static Item, name: TextComponent, lore: Vec<TextComponent>) -> ItemStack {
+    let mut stack = ItemStack::new(1, item);
+    stack.patch.push((DataComponent::CustomName, Some(CustomNameImpl { name }.to_dyn())));
+    if !lore.is_empty() {
+        stack.patch.push((DataComponent::Lore, Some(LoreImpl { lines: lore }.to_dyn())));
+    }
+    stack
+}
+
+/// Contenu du coffre : une page de la palette et la ligne des onglets
+#[derive(Debug)]
+pub struct ColorPickerInventory {
+    items: Vec<Arc<Mutex<ItemStack>>>,
+}
+
+impl ColorPickerInventory {
+    pub fn new() -> Self {
+        Self {
+            items: (0..GUI_SIZE).map(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))).collect(),
+        }
+    }
+
+    /// Remplit le coffre pour un onglet et une page (à partir de 0).
+    /// Retourne le nom de l'entrée de chaque slot (`None` pour un slot laissé vide).
+    pub async fn fill(&self, player: &Player, tab: ColorTab, page: usize) -> Vec<Option<String>> {
+        self.clear().await;
+
+        let colors = get_available_colors().await;
+        let current = match tab.current_style(player).await {
+            Some(style) => Some(style_name(&style).await),
+            None => None,
+        };
+
+        // Indexé par slot : une entrée sans style laisse son slot vide
+        let mut shown = Vec::new();
+        for (slot, name) in colors.iter().skip(page * GUI_PAGE_SIZE).take(GUI_PAGE_SIZE).enumerate() {
+            let Some(style) = resolve_style(name).await else {
+                shown.push(None);
+                continue;
+            };
+            let (label, category, description) = describe_entry(name, &style).await;
+            let mut description = vec![TextComponent::text(description).color_named(NamedColor::Gray)];
+            if let Some(category) = category {
+                description.push(TextComponent::text(category).color_named(NamedColor::DarkGray));
+            }
+
+            // Entrée verrouillée : barrière, avec la permission manquante dans la description
+            let stack = if can_use_color(player, name).await {
+                let mut lore = description;
+                if current.as_deref() == Some(name.as_str()) {
+                    lore.push(TextComponent::text("Current").color_named(NamedColor::Green));
+                } else {
+                    lore.push(TextComponent::text("Click to use it").color_named(NamedColor::Yellow));
+                }
+                // Objet choisi dans la config, sinon laine de la couleur du style
+                let icon = {
+                    let config = PLUGIN_CONFIG.lock().await;
+                    entry_meta(&config, name)
+                        .and_then(|meta| meta.icon.as_deref())
+                        .and_then(|icon| Item::from_registry_key(icon.strip_prefix("minecraft:").unwrap_or(icon)))
+                };
+                let icon = match icon {
+                    Some(icon) => icon,
+                    None => wool_for(dominant_color(&style).await),
+                };
+                display_item(icon, apply_style(&label, &style).await, lore)
+            } else {
+                display_item(
+                    &Item::BARRIER,
+                    TextComponent::text(label).color_named(NamedColor::DarkGray).strikethrough(),
+                    description.into_iter()
+                        .chain([TextComponent::text(format!("Locked: unlock it or get {}", color_permission(name)))
+                            .color_named(NamedColor::Red)])
+                        .collect(),
+                )
+            };
+            self.set_stack(slot, stack).await;
+            shown.push(Some(name.clone()));
+        }
+
+        // Ligne des onglets et de la navigation
+        for (slot, item, tab_of_slot) in [
+            (SLOT_CHAT_TAB, &Item::PAPER, ColorTab::Chat),
+            (SLOT_NAME_TAB, &Item::NAME_TAG, ColorTab::Name),
+        ] {
+            let label = if tab_of_slot == tab {
+                TextComponent::text(format!("» {} «", tab_of_slot.title())).color_named(NamedColor::Gold)
+            } else {
+                TextComponent::text(tab_of_slot.title()).color_named(NamedColor::Yellow)
+            };
+            self.set_stack(slot, display_item(item, label, Vec::new())).await;
+        }
+        let page_count = colors.len().div_ceil(GUI_PAGE_SIZE).max(1);
+        if page > 0 {
+            let label = TextComponent::text(format!("Page {}/{}", page, page_count)).color_named(NamedColor::Yellow);
+            self.set_stack(SLOT_PREVIOUS, display_item(&Item::ARROW, label, Vec::new())).await;
+        }
+        if page + 1 < page_count {
+            let label = TextComponent::text(format!("Page {}/{}", page + 2, page_count)).color_named(NamedColor::Yellow);
+            self.set_stack(SLOT_NEXT, display_item(&Item::ARROW, label, Vec::new())).await;
+        }
+        shown
+    }
+}
+
+impl Default for ColorPickerInventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Clearable for ColorPickerInventory {
+    async fn clear(&self) {
+        for item in &self.items {
+            *item.lock().await = ItemStack::EMPTY.clone();
+        }
+    }
+}
+
+#[async_trait]
+impl Inventory for ColorPickerInventory {
+    fn size(&self) -> usize {
+        self.items.len()
+    }
+
+    async fn is_empty(&self) -> bool {
+        for item in &self.items {
+            if !item.lock().await.is_empty() {
+                return false;
+            }
+        }
+        true
+    }
+
+    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+        self.items[slot].clone()
+    }
+
+    // Les objets du sélecteur ne peuvent pas être retirés
+    async fn remove_stack(&self, _slot: usize) -> ItemStack {
+        ItemStack::EMPTY.clone()
+    }
+
+    async fn remove_stack_specific(&self, _slot: usize, _amount: u8) -> ItemStack {
+        ItemStack::EMPTY.clone()
+    }
+
+    async fn set_stack(&self, slot: usize, stack: ItemStack) {
+        *self.items[slot].lock().await = stack;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Écran du sélecteur : les clics choisissent une entrée au lieu de déplacer les objets
+pub struct ColorPickerScreenHandler {
+    behaviour: ScreenHandlerBehaviour,
+    inventory: Arc<ColorPickerInventory>,
+    player: Arc<Player>,
+    tab: ColorTab,
+    page: usize,
+    shown: Vec<Option<String>>,
+}
+
+impl ColorPickerScreenHandler {
+    async fn new(
+        sync_id: u8,
+        player_inventory: &Arc<PlayerInventory>,
+        player: Arc<Player>,
+        tab: ColorTab,
+    ) -> Self {
+        let inventory = Arc::new(ColorPickerInventory::new());
+        let shown = inventory.fill(&player, tab, 0).await;
+        let mut handler = Self {
+            behaviour: ScreenHandlerBehaviour::new(sync_id, Some(WindowType::Generic9x6)),
+            inventory: inventory.clone(),
+            player,
+            tab,
+            page: 0,
+            shown,
+        };
+        for slot in 0..GUI_SIZE {
+            handler.add_slot(Arc::new(NormalSlot::new(inventory.clone(), slot)));
+        }
+        handler.add_player_slots(player_inventory);
+        handler
+    }
+
+    /// Recharge le contenu (après un changement d'onglet, de page ou de style)
+    async fn refresh(&mut self) {
+        self.shown = self.inventory.fill(&self.player, self.tab, self.page).await;
+    }
+
+    /// Applique une entrée en passant par la commande, qui vérifie la permission et sauvegarde
+    async fn choose(&mut self, name: &str) {
+        let Some(server) = SERVER.lock().await.upgrade() else {
+            return;
+        };
+        let command = format!("{} {}", self.tab.command(), name);
+        let dispatcher = server.command_dispatcher.read().await;
+        dispatcher
+            .handle_command(&mut CommandSender::Player(self.player.clone()), &server, &command)
+            .await;
+    }
+}
+
+#[async_trait]
+impl ScreenHandler for ColorPickerScreenHandler {
+    fn window_type(&self) -> Option<WindowType> {
+        Some(WindowType::Generic9x6)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn size(&self) -> usize {
+        self.behaviour.slots.len()
+    }
+
+    fn get_behaviour(&self) -> &ScreenHandlerBehaviour {
+        &self.behaviour
+    }
+
+    fn get_behaviour_mut(&mut self) -> &mut ScreenHandlerBehaviour {
+        &mut self.behaviour
+    }
+
+    async fn quick_move(&mut self, _player: &dyn InventoryPlayer, _slot_index: i32) -> ItemStack {
+        ItemStack::EMPTY.clone()
+    }
+
+    async fn on_slot_click(
+        &mut self,
+        slot_index: i32,
+        _button: i32,
+        _action_type: pumpkin_inventory::screen_handler::SlotActionType,
+        _player: &dyn InventoryPlayer,
+    ) {
+        if let Ok(slot) = usize::try_from(slot_index) {
+            match slot {
+                SLOT_CHAT_TAB => {
+                    self.tab = ColorTab::Chat;
+                    self.page = 0;
+                }
+                SLOT_NAME_TAB => {
+                    self.tab = ColorTab::Name;
+                    self.page = 0;
+                }
+                SLOT_PREVIOUS => self.page = self.page.saturating_sub(1),
+                SLOT_NEXT if get_available_colors().await.len() > (self.page + 1) * GUI_PAGE_SIZE => self.page += 1,
+                slot if slot < GUI_PAGE_SIZE => {
+                    if let Some(name) = self.shown.get(slot).cloned().flatten() {
+                        if can_use_color(&self.player, &name).await {
+                            self.choose(&name).await;
+                        } else {
+                            self.player.send_system_message(&TextComponent::text(format!(
+                                "You don't have permission to use {} ({}).",
+                                display_name(&name).await, color_permission(&name)
+                            ))).await;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Aucun objet ne bouge : on renvoie tout l'état au client
+        self.refresh().await;
+        self.sync_state().await;
+    }
+}
+
+/// Ouvre le sélecteur en coffre pour un joueur
+pub struct ColorPickerFactory {
+    pub player: Arc<Player>,
+    pub tab: ColorTab,
+}
+
+#[async_trait]
+impl ScreenHandlerFactory for ColorPickerFactory {
+    async fn create_screen_handler(
+        &self,
+        sync_id: u8,
+        player_inventory: &Arc<PlayerInventory>,
+        _player: &dyn InventoryPlayer,
+    ) -> Option<Arc<Mutex<dyn ScreenHandler>>> {
+        let handler = ColorPickerScreenHandler::new(sync_id, player_inventory, self.player.clone(), self.tab).await;
+        Some(Arc::new(Mutex::new(handler)))
+    }
+
+    fn get_display_name(&self) -> TextComponent {
+        TextComponent::text(self.tab.title())
+    }
+}
+
+/// Ouvre le sélecteur de couleurs sur l'onglet demandé
+pub async fn open_color_gui(player: &Arc<Player>, tab: ColorTab) {
+    let factory = ColorPickerFactory { player: player.clone(), tab };
+    player.open_handled_screen(&factory).await;
+}
@@ -0,0 +1,162 @@
+use std::borrow::Cow;
+use pumpkin::entity::player::Player;
+use pumpkin_util::text::{click::ClickEvent, color::NamedColor, hover::HoverEvent, TextComponent};
+use crate::config::{ChatColorStyle, GradientMethod};
+use crate::storage::{PLAYER_UNLOCKS, PLUGIN_CONFIG};
+use crate::utils::{apply_style, entry_display_name, entry_meta, style_name};
+use crate::commands::chatcolor::{get_available_colors, resolve_style};
+
+/// Nombre d'entrées de palette par page du menu
+pub const MENU_PAGE_SIZE: usize = 8;
+
+/// Permission nécessaire pour utiliser une entrée de la palette
+pub fn color_permission(color_name: &str) -> String {
+    format!("chat-color:color.{}", color_name.to_lowercase())
+}
+
+/// Indique si le joueur a débloqué une entrée de la palette (récompense, achat...)
+pub async fn has_unlocked_color(player: &Player, color_name: &str) -> bool {
+    PLAYER_UNLOCKS.lock().await
+        .get(&player.gameprofile.id)
+        .is_some_and(|unlocked| unlocked.contains(&color_name.to_lowercase()))
+}
+
+/// Vérifie que le joueur peut utiliser une entrée de la palette : débloquée ou permise par permission
+pub async fn can_use_color(player: &Player, color_name: &str) -> bool {
+    has_unlocked_color(player, color_name).await || player.has_permission(&color_permission(color_name)).await
+}
+
+/// Description courte d'un style pour les bulles d'aide
+pub async fn describe_style(style: &ChatColorStyle) -> String {
+    match style {
+        ChatColorStyle::Simple(_) => "Simple color".to_string(),
+        ChatColorStyle::Rainbow | ChatColorStyle::Fire => "Gradient".to_string(),
+        ChatColorStyle::CustomGradient(gradient_name) => {
+            let config = PLUGIN_CONFIG.lock().await;
+            match config.gradients.get(gradient_name) {
+                Some(gradient) => match gradient.method {
+                    GradientMethod::Hsv => "HSV gradient".to_string(),
+                    GradientMethod::RgbInterpolation => format!(
+                        "Gradient of {} colors",
+                        gradient.colors.as_ref().map_or(0, |colors| colors.len())
+                    ),
+                },
+                None => "Gradient".to_string(),
+            }
+        }
+        ChatColorStyle::PersonalGradient(stops) => format!("Personal gradient of {} colors", stops.len()),
+    }
+}
+
+/// Nom affiché, catégorie et description d'une entrée (la description de la config,
+/// sinon celle du style)
+pub async fn describe_entry(name: &str, style: &ChatColorStyle) -> (String, Option<String>, String) {
+    let (label, category, description) = {
+        let config = PLUGIN_CONFIG.lock().await;
+        let meta = entry_meta(&config, name);
+        (
+            entry_display_name(&config, name),
+            meta.and_then(|meta| meta.category.clone()),
+            meta.and_then(|meta| meta.description.clone()),
+        )
+    };
+    let description = match description {
+        Some(description) => description,
+        None => describe_style(style).await,
+    };
+    (label, category, description)
+}
+
+/// Menu cliquable de la palette : chaque entrée dans son style, un clic lance `/<command> <nom>`.
+/// `current` est le style actuel du joueur, marqué dans la liste.
+pub async fn build_color_menu(
+    player: &Player,
+    command: &str,
+    title: &str,
+    current: Option<&ChatColorStyle>,
+    page: usize,
+) -> TextComponent {
+    let colors = get_available_colors().await;
+    let page_count = colors.len().div_ceil(MENU_PAGE_SIZE).max(1);
+    let page = page.clamp(1, page_count);
+    let current_name = match current {
+        Some(style) => Some(style_name(style).await),
+        None => None,
+    };
+
+    let mut menu = TextComponent::text(format!("--- {} (page {}/{}) ---", title, page, page_count))
+        .color_named(NamedColor::Gold);
+
+    let mut last_category = None;
+    for name in colors.iter().skip((page - 1) * MENU_PAGE_SIZE).take(MENU_PAGE_SIZE) {
+        let Some(style) = resolve_style(name).await else {
+            continue;
+        };
+        let permission = color_permission(name);
+        let (label, category, description) = describe_entry(name, &style).await;
+
+        // En-tête à chaque nouvelle catégorie
+        if category.is_some() && category != last_category {
+            menu = menu.add_child(
+                TextComponent::text(format!("\n {}", category.clone().unwrap_or_default()))
+                    .color_named(NamedColor::Yellow)
+                    .bold(),
+            );
+        }
+        last_category = category;
+
+        let entry = if can_use_color(player, name).await {
+            let is_current = current_name.as_deref() == Some(name.as_str());
+            let marker = if is_current { "\n ✔ " } else { "\n » " };
+            TextComponent::text(marker)
+                .color_named(NamedColor::Gray)
+                .add_child(
+                    apply_style(&label, &style).await
+                        .click_event(ClickEvent::RunCommand(Cow::from(format!("/{} {}", command, name))))
+                        .hover_event(HoverEvent::show_text(TextComponent::text(format!(
+                            "{}\nClick to use it\nPermission: {}",
+                            description, permission
+                        )))),
+                )
+        } else {
+            TextComponent::text("\n ✖ ")
+                .color_named(NamedColor::DarkGray)
+                .add_child(
+                    TextComponent::text(label)
+                        .color_named(NamedColor::DarkGray)
+                        .strikethrough()
+                        .hover_event(HoverEvent::show_text(TextComponent::text(format!(
+                            "{}\nLocked: unlock it or get {}",
+                            description, permission
+                        )))),
+                )
+        };
+        menu = menu.add_child(entry);
+    }
+
+    // Navigation entre les pages
+    let mut navigation = TextComponent::text("\n");
+    if page > 1 {
+        navigation = navigation.add_child(
+            TextComponent::text("[« Previous]")
+                .color_named(NamedColor::Yellow)
+                .click_event(ClickEvent::RunCommand(Cow::from(format!("/{} menu {}", command, page - 1))))
+                .hover_event(HoverEvent::show_text(TextComponent::text(format!("Page {}", page - 1)))),
+        );
+    }
+    if page > 1 && page < page_count {
+        navigation = navigation.add_child(TextComponent::text("  "));
+    }
+    if page < page_count {
+        navigation = navigation.add_child(
+            TextComponent::text("[Next »]")
+                .color_named(NamedColor::Yellow)
+                .click_event(ClickEvent::RunCommand(Cow::from(format!("/{} menu {}", command, page + 1))))
+                .hover_event(HoverEvent::show_text(TextComponent::text(format!("Page {}", page + 1)))),
+        );
+    }
+    if page_count > 1 {
+        menu = menu.add_child(navigation);
+    }
+    menu
+}
@@ -167,6 +167,14 @@ fn apply_format_code(component: TextComponent, format: FormatCode) -> TextCompon
     }
 }
 
+/// Applique une suite de codes de mise en forme (ex: "&l&o") à tout un TextComponent.
+/// Les `&`/`§` et les codes qui ne sont pas des mises en forme sont ignorés.
+pub fn apply_format_codes(component: TextComponent, codes: &str) -> TextComponent {
+    codes.chars()
+        .filter_map(|ch| parse_format_code(ch.to_ascii_lowercase()))
+        .fold(component, apply_format_code)
+}
+
 /// Applique un gradient arc-en-ciel lettre par lettre sur le texte.
 pub fn apply_rainbow_gradient(text: &str) -> TextComponent {
     let chars: Vec<char> = text.chars().collect();
@@ -205,7 +213,8 @@ pub fn apply_fire_gradient(text: &str) -> TextComponent {
 
 // Utilitaires
 pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
-    let h = h * 360.0;
+    // Teinte de 0 à 1, 1.0 (360°) revient au rouge
+    let h = (h * 360.0).rem_euclid(360.0);
     let c = v * s;
     let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
     let m = v - c;
@@ -264,6 +273,58 @@ pub async fn apply_custom_gradient(text: &str, gradient_name: &str) -> TextCompo
     }
 }
 
+/// Applique le style d'un joueur (couleur simple ou gradient) à un texte.
+/// Les couleurs trop sombres pour le fond du chat sont éclaircies si la config le demande.
+pub async fn apply_style(text: &str, style: &crate::config::ChatColorStyle) -> TextComponent {
+    use crate::config::ChatColorStyle;
+    let styled = match style {
+        ChatColorStyle::Simple(color) => {
+            parse_color_codes(&format!("&{}{}", crate::utils::color_to_code(*color), text))
+        },
+        ChatColorStyle::Rainbow => apply_rainbow_gradient(text),
+        ChatColorStyle::Fire => apply_fire_gradient(text),
+        ChatColorStyle::CustomGradient(gradient_name) => apply_custom_gradient(text, gradient_name).await,
+        ChatColorStyle::PersonalGradient(stops) => apply_rgb_gradient(text, stops),
+    };
+    let readability = crate::storage::PLUGIN_CONFIG.lock().await.readability.clone();
+    crate::utils::lighten_component(styled, &readability)
+}
+
+/// Pseudo du joueur avec sa couleur de pseudo (ou sans style s'il n'en a pas)
+pub async fn styled_player_name(player: &pumpkin::entity::player::Player) -> TextComponent {
+    let style = crate::storage::PLAYER_NAME_COLORS.lock().await.get(&player.gameprofile.id).cloned();
+    let name = match style {
+        Some(style) => apply_style(&player.gameprofile.name, &style).await,
+        None => TextComponent::text(player.gameprofile.name.clone()),
+    };
+    // Mise en forme venant d'un preset (ex: gras)
+    match crate::storage::PLAYER_NAME_FORMATS.lock().await.get(&player.gameprofile.id) {
+        Some(codes) => apply_format_codes(name, codes),
+        None => name,
+    }
+}
+
+/// Texte écrit par le joueur avec sa couleur de chat, ou `fallback_style` s'il n'en a pas.
+/// Ses propres codes & (s'il a la permission) remplacent une couleur simple.
+pub async fn styled_player_message(
+    player: &pumpkin::entity::player::Player,
+    message: &str,
+    fallback_style: Option<crate::config::ChatColorStyle>,
+) -> TextComponent {
+    use crate::config::ChatColorStyle;
+    let message = crate::utils::filter_player_codes(player, message).await;
+    let style = crate::storage::PLAYER_COLORS.lock().await.get(&player.gameprofile.id).cloned().or(fallback_style);
+    let styled = match style {
+        Some(ChatColorStyle::Simple(_)) if message.contains('&') => parse_color_codes(&message),
+        Some(style) => apply_style(&message, &style).await,
+        None => parse_color_codes(&message),
+    };
+    match crate::storage::PLAYER_MESSAGE_FORMATS.lock().await.get(&player.gameprofile.id) {
+        Some(codes) => apply_format_codes(styled, codes),
+        None => styled,
+    }
+}
+
 // Fonction pour appliquer un gradient HSV personnalisé
 fn apply_hsv_gradient(text: &str, start_hue: f32, end_hue: f32, saturation: f32, value: f32) -> TextComponent {
     let chars: Vec<char> = text.chars().collect();
@@ -273,7 +334,7 @@ fn apply_hsv_gradient(text: &str, start_hue: f32, end_hue: f32, saturation: f32,
     for (i, c) in chars.iter().enumerate() {
         let t = (i as f32) / (len as f32);
         let hue = start_hue + (end_hue - start_hue) * t;
-        let rgb = hsv_to_rgb(hue, saturation, value);
+        let rgb = hsv_to_rgb(hue / 360.0, saturation, value);
         let color = Color::Rgb(RGBColor::new(rgb.0, rgb.1, rgb.2));
         let letter = TextComponent::text(c.to_string()).color(color);
         component = component.add_child(letter);
@@ -373,6 +434,24 @@ mod tests {
         assert_eq!(parse_format_code('x'), None);
     }
 
+    #[test]
+    fn test_hsv_to_rgb_wraps_full_turn() {
+        assert_eq!(hsv_to_rgb(0.0, 1.0, 1.0), (255, 0, 0));
+        assert_eq!(hsv_to_rgb(1.0, 1.0, 1.0), (255, 0, 0));
+        assert_eq!(hsv_to_rgb(2.0 / 3.0, 1.0, 1.0), (0, 0, 255));
+    }
+
+    #[test]
+    fn test_apply_format_codes() {
+        let result = apply_format_codes(TextComponent::text("Royal"), "&l§c&o");
+        assert_eq!(result.get_text(), "Royal");
+        assert_eq!(result.0.style.bold, Some(true));
+        assert_eq!(result.0.style.italic, Some(true));
+        // Les codes couleur sont ignorés : seule la mise en forme est appliquée
+        assert!(result.0.style.color.is_none());
+        assert_eq!(result.0.style.underlined, None);
+    }
+
     #[test]
     fn test_apply_glowing_effect() {
         let result = apply_glowing_effect("Hello");
@@ -20,4 +20,71 @@ pub fn color_to_code(color: NamedColor) -> char {
         NamedColor::Yellow => 'e',
         NamedColor::White => 'f',
     }
-} 
\ No newline at end of file
+} 
+// Valeurs RGB des couleurs nommées Minecraft (palette vanilla)
+pub fn named_color_rgb(color: NamedColor) -> (u8, u8, u8) {
+    match color {
+        NamedColor::Black => (0, 0, 0),
+        NamedColor::DarkBlue => (0, 0, 170),
+        NamedColor::DarkGreen => (0, 170, 0),
+        NamedColor::DarkAqua => (0, 170, 170),
+        NamedColor::DarkRed => (170, 0, 0),
+        NamedColor::DarkPurple => (170, 0, 170),
+        NamedColor::Gold => (255, 170, 0),
+        NamedColor::Gray => (170, 170, 170),
+        NamedColor::DarkGray => (85, 85, 85),
+        NamedColor::Blue => (85, 85, 255),
+        NamedColor::Green => (85, 255, 85),
+        NamedColor::Aqua => (85, 255, 255),
+        NamedColor::Red => (255, 85, 85),
+        NamedColor::LightPurple => (255, 85, 255),
+        NamedColor::Yellow => (255, 255, 85),
+        NamedColor::White => (255, 255, 255),
+    }
+}
+
+const ALL_NAMED_COLORS: [NamedColor; 16] = [
+    NamedColor::Black, NamedColor::DarkBlue, NamedColor::DarkGreen, NamedColor::DarkAqua,
+    NamedColor::DarkRed, NamedColor::DarkPurple, NamedColor::Gold, NamedColor::Gray,
+    NamedColor::DarkGray, NamedColor::Blue, NamedColor::Green, NamedColor::Aqua,
+    NamedColor::Red, NamedColor::LightPurple, NamedColor::Yellow, NamedColor::White,
+];
+
+// Trouve la couleur nommée la plus proche d'une couleur RGB (distance euclidienne)
+pub fn nearest_named_color(rgb: (u8, u8, u8)) -> NamedColor {
+    let distance = |color: NamedColor| {
+        let (r, g, b) = named_color_rgb(color);
+        let dr = r as i32 - rgb.0 as i32;
+        let dg = g as i32 - rgb.1 as i32;
+        let db = b as i32 - rgb.2 as i32;
+        dr * dr + dg * dg + db * db
+    };
+    ALL_NAMED_COLORS
+        .iter()
+        .copied()
+        .min_by_key(|color| distance(*color))
+        .unwrap_or(NamedColor::White)
+}
+
+// Convertit une couleur hexadécimale (#ff8800 ou ff8800) en RGB
+pub fn parse_hex_color(hex: &str) -> Option<[u8; 3]> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#ff8800"), Some([255, 136, 0]));
+        assert_eq!(parse_hex_color("00FF7f"), Some([0, 255, 127]));
+        assert_eq!(parse_hex_color("#fff"), None);
+        assert_eq!(parse_hex_color("#gg0000"), None);
+    }
+}
@@ -0,0 +1,34 @@
+use pumpkin_util::text::{TextComponent, TextComponentBase, TextContent};
+
+/// Applique `f` à chaque nœud de l'arbre du composant (enfants et arguments de traduction compris)
+pub fn walk_component_mut(base: &mut TextComponentBase, f: &mut impl FnMut(&mut TextComponentBase)) {
+    f(base);
+    if let TextContent::Translate { with, .. } = base.content.as_mut() {
+        for arg in with.iter_mut() {
+            walk_component_mut(arg, f);
+        }
+    }
+    for child in base.extra.iter_mut() {
+        walk_component_mut(child, f);
+    }
+}
+
+/// Remplace, dans un message (ex: message de mort traduit), chaque texte égal à un pseudo
+/// par sa version stylée. Les événements de clic / survol d'origine sont conservés.
+pub fn restyle_player_names(component: TextComponent, names: &[(String, TextComponent)]) -> TextComponent {
+    let mut base = component.0;
+    walk_component_mut(&mut base, &mut |node| {
+        let TextContent::Text { text } = node.content.as_ref() else {
+            return;
+        };
+        let Some((_, styled)) = names.iter().find(|(name, _)| name.as_str() == text.as_ref()) else {
+            return;
+        };
+        let mut replacement = styled.0.clone();
+        replacement.style.click_event = node.style.click_event.take();
+        replacement.style.hover_event = node.style.hover_event.take();
+        replacement.extra.append(&mut node.extra);
+        *node = replacement;
+    });
+    TextComponent(base)
+}
@@ -0,0 +1,140 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use pumpkin::entity::player::Player;
+use pumpkin::server::Server;
+use pumpkin_util::text::TextComponent;
+use uuid::Uuid;
+use crate::config::{ChatColorStyle, ColorGrant};
+use crate::storage::{PLAYER_COLORS, PLAYER_GRANTS};
+use crate::utils::save_data;
+
+/// Intervalle de vérification des couleurs temporaires expirées (en secondes)
+pub const GRANT_CHECK_INTERVAL: u64 = 60;
+
+/// Heure actuelle en secondes depuis l'époque Unix
+pub fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// Convertit une durée (ex: `7d`, `12h`, `30m`, `1d12h`) en secondes
+pub fn parse_duration(input: &str) -> Option<u64> {
+    let mut total = 0u64;
+    let mut number = String::new();
+    for ch in input.chars() {
+        if ch.is_ascii_digit() {
+            number.push(ch);
+            continue;
+        }
+        let unit = match ch.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            'w' => 604800,
+            _ => return None,
+        };
+        let value: u64 = number.parse().ok()?;
+        total = total.checked_add(value.checked_mul(unit)?)?;
+        number.clear();
+    }
+    // Un nombre sans unité ou une durée nulle n'est pas valide
+    if !number.is_empty() || total == 0 {
+        return None;
+    }
+    Some(total)
+}
+
+/// Donne un style de chat pour une durée. Le style d'avant la première couleur temporaire
+/// est conservé si une nouvelle couleur temporaire remplace la précédente.
+pub async fn grant_style(uuid: Uuid, style: ChatColorStyle, duration_secs: u64) {
+    let existing = PLAYER_GRANTS.lock().await.get(&uuid).map(|grant| grant.previous.clone());
+    let previous = match existing {
+        Some(previous) => previous,
+        None => PLAYER_COLORS.lock().await.get(&uuid).cloned(),
+    };
+    PLAYER_COLORS.lock().await.insert(uuid, style.clone());
+    PLAYER_GRANTS.lock().await.insert(uuid, ColorGrant {
+        style,
+        previous,
+        expires_at: now_secs().saturating_add(duration_secs),
+    });
+}
+
+/// Retire une couleur temporaire expirée : le joueur retrouve son style précédent,
+/// sauf s'il a changé de couleur entre-temps
+async fn revert_grant(uuid: &Uuid, grant: ColorGrant) {
+    let mut colors = PLAYER_COLORS.lock().await;
+    if colors.get(uuid) != Some(&grant.style) {
+        return;
+    }
+    match grant.previous {
+        Some(previous) => colors.insert(*uuid, previous),
+        None => colors.remove(uuid),
+    };
+}
+
+/// Retire les couleurs temporaires expirées et prévient les joueurs en ligne
+pub async fn expire_grants(server: &Server) {
+    let now = now_secs();
+    let expired: Vec<(Uuid, ColorGrant)> = {
+        let mut grants = PLAYER_GRANTS.lock().await;
+        let uuids: Vec<Uuid> = grants.iter()
+            .filter(|(_, grant)| grant.expires_at <= now)
+            .map(|(uuid, _)| *uuid)
+            .collect();
+        uuids.into_iter().filter_map(|uuid| grants.remove(&uuid).map(|grant| (uuid, grant))).collect()
+    };
+    if expired.is_empty() {
+        return;
+    }
+
+    for (uuid, grant) in expired {
+        revert_grant(&uuid, grant).await;
+        if let Some(player) = server.get_player_by_uuid(uuid).await {
+            notify_expired(&player).await;
+        }
+    }
+
+    if let Err(e) = save_data().await {
+        log::error!("[ChatColor] Failed to save player data: {}", e);
+    }
+}
+
+/// Vérifie la couleur temporaire d'un joueur (à la connexion)
+pub async fn expire_player_grant(player: &Player) {
+    let uuid = player.gameprofile.id;
+    let grant = {
+        let mut grants = PLAYER_GRANTS.lock().await;
+        let expired = grants.get(&uuid).is_some_and(|grant| grant.expires_at <= now_secs());
+        if expired { grants.remove(&uuid) } else { None }
+    };
+    let Some(grant) = grant else {
+        return;
+    };
+
+    revert_grant(&uuid, grant).await;
+    notify_expired(player).await;
+    if let Err(e) = save_data().await {
+        log::error!("[ChatColor] Failed to save player data: {}", e);
+    }
+}
+
+async fn notify_expired(player: &Player) {
+    player.send_system_message(&TextComponent::text(
+        "Your temporary chat color has expired, your previous color is back."
+    )).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("7d"), Some(7 * 86400));
+        assert_eq!(parse_duration("1d12h"), Some(86400 + 12 * 3600));
+        assert_eq!(parse_duration("30m"), Some(1800));
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration("0d"), None);
+        assert_eq!(parse_duration("5x"), None);
+    }
+}
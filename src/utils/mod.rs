@@ -1,7 +1,41 @@
 pub mod yaml_manager;
 pub mod color_parser;
 pub mod color_utils;
+pub mod team_manager;
+pub mod chat_format;
+pub mod tags;
+pub mod tab_list;
+pub mod placeholders;
+pub mod channels;
+pub mod code_filter;
+pub mod component_utils;
+pub mod color_menu;
+pub mod color_gui;
+pub mod readability;
+pub mod accessibility;
+pub mod grants;
+pub mod seasons;
+pub mod palette;
+pub mod presets;
+pub mod targets;
 
 pub use yaml_manager::*;
 pub use color_parser::*;
-pub use color_utils::*; 
\ No newline at end of file
+pub use color_utils::*;
+pub use team_manager::*;
+pub use chat_format::*;
+pub use tags::*;
+pub use tab_list::*;
+pub use placeholders::*;
+pub use channels::*;
+pub use code_filter::*;
+pub use component_utils::*;
+pub use color_menu::*;
+pub use color_gui::*;
+pub use readability::*;
+pub use accessibility::*;
+pub use grants::*;
+pub use seasons::*;
+pub use palette::*;
+pub use presets::*;
+pub use targets::*; 
\ No newline at end of file
@@ -0,0 +1,73 @@
+use crate::config::{EntryMeta, PluginConfig};
+use crate::storage::PLUGIN_CONFIG;
+
+/// Informations d'affichage d'une entrée de la palette (couleur simple ou gradient)
+pub fn entry_meta<'a>(config: &'a PluginConfig, name: &str) -> Option<&'a EntryMeta> {
+    match config.simple_colors.get(name) {
+        Some(color) => color.meta(),
+        None => config.gradients.get(name).map(|gradient| &gradient.meta),
+    }
+}
+
+/// Nom affiché d'une entrée : `display_name` de la config, sinon la clé (`dark_aqua` -> `Dark Aqua`)
+pub fn entry_display_name(config: &PluginConfig, name: &str) -> String {
+    if let Some(display_name) = entry_meta(config, name).and_then(|meta| meta.display_name.clone()) {
+        return display_name;
+    }
+    title_case(name)
+}
+
+/// Clé de config mise en forme pour l'affichage (`dark_aqua` -> `Dark Aqua`)
+pub fn title_case(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Nom affiché d'une entrée de la palette
+pub async fn display_name(name: &str) -> String {
+    let config = PLUGIN_CONFIG.lock().await;
+    entry_display_name(&config, &name.to_lowercase())
+}
+
+/// Toutes les entrées de la palette, dans un ordre stable : par catégorie (sans catégorie en premier),
+/// puis `sort_order`, puis nom affiché
+pub fn palette_names(config: &PluginConfig) -> Vec<String> {
+    let mut names: Vec<String> = config.simple_colors.keys()
+        .chain(config.gradients.keys())
+        .cloned()
+        .collect();
+    names.sort_by_cached_key(|name| {
+        let meta = entry_meta(config, name);
+        (
+            meta.and_then(|meta| meta.category.clone()).unwrap_or_default(),
+            meta.map_or(0, |meta| meta.sort_order),
+            entry_display_name(config, name).to_lowercase(),
+            name.clone(),
+        )
+    });
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_display_name_from_key() {
+        let config: PluginConfig = serde_yaml::from_str(
+            "save_interval: 300\nauto_save: true\nsimple_colors:\n  dark_aqua: \"§3\"\n  red:\n    code: \"§c\"\n    display_name: \"Crimson\"\ngradients: {}\nsettings:\n  default_chat_color: white\n  default_name_color: white\n"
+        ).unwrap();
+        assert_eq!(entry_display_name(&config, "dark_aqua"), "Dark Aqua");
+        assert_eq!(entry_display_name(&config, "red"), "Crimson");
+    }
+}
@@ -0,0 +1,264 @@
+//! Registre de placeholders (`{name}`, `{world}`, ...) utilisables dans toutes les chaînes
+//! de la config rendues par le plugin.
+//!
+//! Les autres plugins du serveur peuvent enregistrer leurs propres placeholders :
+//!
+//! ```ignore
+//! struct Balance;
+//!
+//! #[async_trait::async_trait]
+//! impl PlaceholderProvider for Balance {
+//!     async fn resolve(&self, player: &Player) -> Option<String> {
+//!         Some(format!("{}$", get_balance(player).await))
+//!     }
+//! }
+//!
+//! chat_color::utils::register_placeholder("balance", Arc::new(Balance)).await;
+//! ```
+//!
+//! L'expansion se fait en une seule passe : une valeur résolue n'est jamais ré-analysée,
+//! et `{{` produit un `{` littéral. Les valeurs sont insérées en texte brut (leurs codes & sont
+//! retirés), dans les formats comme dans les tags et les préfixes de canaux.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::{SystemTime, UNIX_EPOCH};
+use once_cell::sync::Lazy;
+use pumpkin::entity::player::Player;
+use tokio::sync::RwLock;
+use crate::config::ChatColorStyle;
+use crate::storage::{PLAYER_COLORS, PLAYER_NAME_COLORS, PLUGIN_CONFIG};
+use crate::utils::{color_to_code, strip_color_codes};
+
+/// Fournit la valeur d'un placeholder pour un joueur donné
+#[async_trait::async_trait]
+pub trait PlaceholderProvider: Send + Sync {
+    async fn resolve(&self, player: &Player) -> Option<String>;
+}
+
+static PLACEHOLDERS: Lazy<RwLock<HashMap<String, Arc<dyn PlaceholderProvider>>>> =
+    Lazy::new(|| RwLock::new(builtin_placeholders()));
+
+/// Enregistre (ou remplace) un placeholder. La clé est utilisée sans accolades : `"balance"` pour `{balance}`.
+pub async fn register_placeholder(key: &str, provider: Arc<dyn PlaceholderProvider>) {
+    PLACEHOLDERS.write().await.insert(key.to_lowercase(), provider);
+}
+
+/// Retire un placeholder du registre
+pub async fn unregister_placeholder(key: &str) -> bool {
+    PLACEHOLDERS.write().await.remove(&key.to_lowercase()).is_some()
+}
+
+/// Liste les clés `{clé}` présentes dans un template (en ignorant les `{{` échappés)
+pub fn placeholder_keys(template: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if rest[start + 1..].starts_with('{') {
+            rest = &rest[start + 2..];
+            continue;
+        }
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let key = &rest[start + 1..start + len];
+        if !key.is_empty() && !keys.iter().any(|k| k == key) {
+            keys.push(key.to_string());
+        }
+        rest = &rest[start + len + 1..];
+    }
+    keys
+}
+
+/// Résout les placeholders enregistrés présents dans le template pour ce joueur
+pub async fn resolve_placeholders(template: &str, player: &Player) -> HashMap<String, String> {
+    let providers: Vec<(String, Arc<dyn PlaceholderProvider>)> = {
+        let registry = PLACEHOLDERS.read().await;
+        placeholder_keys(template)
+            .into_iter()
+            .filter_map(|key| registry.get(&key.to_lowercase()).map(|p| (key, p.clone())))
+            .collect()
+    };
+
+    let mut values = HashMap::new();
+    for (key, provider) in providers {
+        if let Some(value) = provider.resolve(player).await {
+            values.insert(key, value);
+        }
+    }
+    values
+}
+
+/// Remplace les placeholders connus dans une chaîne, en une seule passe.
+/// Les placeholders inconnus restent tels quels, `{{` devient `{`.
+pub fn expand_with(template: &str, values: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        if rest[start + 1..].starts_with('{') {
+            result.push('{');
+            rest = &rest[start + 2..];
+            continue;
+        }
+        let Some(len) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let key = &rest[start + 1..start + len];
+        match values.get(key) {
+            Some(value) => result.push_str(value),
+            None => result.push_str(&rest[start..start + len + 1]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Résout puis remplace les placeholders d'une chaîne pour ce joueur.
+/// Le résultat passe ensuite par `parse_color_codes` : les codes & des valeurs sont retirés
+/// pour qu'un placeholder (parfois d'un autre plugin) ne puisse pas changer le style du texte.
+pub async fn expand_placeholders(template: &str, player: &Player) -> String {
+    let values = resolve_placeholders(template, player).await
+        .into_iter()
+        .map(|(key, value)| (key, strip_color_codes(&value)))
+        .collect();
+    expand_with(template, &values)
+}
+
+// Placeholders intégrés
+
+struct NamePlaceholder;
+
+#[async_trait::async_trait]
+impl PlaceholderProvider for NamePlaceholder {
+    async fn resolve(&self, player: &Player) -> Option<String> {
+        Some(player.gameprofile.name.clone())
+    }
+}
+
+/// Pseudo précédé du code de sa couleur simple (les gradients ne sont pas représentables en codes &)
+struct DisplayNamePlaceholder;
+
+#[async_trait::async_trait]
+impl PlaceholderProvider for DisplayNamePlaceholder {
+    async fn resolve(&self, player: &Player) -> Option<String> {
+        let style = PLAYER_NAME_COLORS.lock().await.get(&player.gameprofile.id).cloned();
+        match style {
+            Some(ChatColorStyle::Simple(color)) => {
+                Some(format!("&{}{}&r", color_to_code(color), player.gameprofile.name))
+            }
+            _ => Some(player.gameprofile.name.clone()),
+        }
+    }
+}
+
+struct WorldPlaceholder;
+
+#[async_trait::async_trait]
+impl PlaceholderProvider for WorldPlaceholder {
+    async fn resolve(&self, player: &Player) -> Option<String> {
+        let world = player.living_entity.entity.world.read().await;
+        Some(format!("{:?}", world.dimension_type).to_lowercase())
+    }
+}
+
+struct UuidPlaceholder;
+
+#[async_trait::async_trait]
+impl PlaceholderProvider for UuidPlaceholder {
+    async fn resolve(&self, player: &Player) -> Option<String> {
+        Some(player.gameprofile.id.to_string())
+    }
+}
+
+struct PingPlaceholder;
+
+#[async_trait::async_trait]
+impl PlaceholderProvider for PingPlaceholder {
+    async fn resolve(&self, player: &Player) -> Option<String> {
+        Some(player.ping.load(Ordering::Relaxed).to_string())
+    }
+}
+
+/// Nom de l'entrée de palette utilisée pour le chat du joueur
+struct ChatColorNamePlaceholder;
+
+#[async_trait::async_trait]
+impl PlaceholderProvider for ChatColorNamePlaceholder {
+    async fn resolve(&self, player: &Player) -> Option<String> {
+        let style = PLAYER_COLORS.lock().await.get(&player.gameprofile.id).cloned();
+        Some(match style {
+            Some(style) => style_name(&style).await,
+            None => "none".to_string(),
+        })
+    }
+}
+
+/// Heure du serveur (UTC) au format HH:MM
+struct TimePlaceholder;
+
+#[async_trait::async_trait]
+impl PlaceholderProvider for TimePlaceholder {
+    async fn resolve(&self, _player: &Player) -> Option<String> {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        Some(format!("{:02}:{:02}", (secs / 3600) % 24, (secs / 60) % 60))
+    }
+}
+
+/// Nom du dégradé personnel (/chatcolor custom), réservé : aucune entrée de palette ne peut le porter
+pub const PERSONAL_GRADIENT_NAME: &str = "custom";
+
+/// Nom de palette correspondant à un style (clé de la config quand elle existe)
+pub async fn style_name(style: &ChatColorStyle) -> String {
+    match style {
+        ChatColorStyle::Simple(color) => {
+            let code = format!("§{}", color_to_code(*color));
+            let config = PLUGIN_CONFIG.lock().await;
+            config.simple_colors.iter()
+                .find(|(_, c)| c.code() == code)
+                .map(|(name, _)| name.clone())
+                .unwrap_or_else(|| format!("{:?}", color).to_lowercase())
+        }
+        ChatColorStyle::Rainbow => "rainbow".to_string(),
+        ChatColorStyle::Fire => "fire".to_string(),
+        ChatColorStyle::CustomGradient(gradient_name) => gradient_name.clone(),
+        ChatColorStyle::PersonalGradient(_) => PERSONAL_GRADIENT_NAME.to_string(),
+    }
+}
+
+fn builtin_placeholders() -> HashMap<String, Arc<dyn PlaceholderProvider>> {
+    let mut map: HashMap<String, Arc<dyn PlaceholderProvider>> = HashMap::new();
+    map.insert("name".to_string(), Arc::new(NamePlaceholder));
+    map.insert("display_name".to_string(), Arc::new(DisplayNamePlaceholder));
+    map.insert("world".to_string(), Arc::new(WorldPlaceholder));
+    map.insert("uuid".to_string(), Arc::new(UuidPlaceholder));
+    map.insert("ping".to_string(), Arc::new(PingPlaceholder));
+    map.insert("chat_color_name".to_string(), Arc::new(ChatColorNamePlaceholder));
+    map.insert("time".to_string(), Arc::new(TimePlaceholder));
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholder_keys() {
+        assert_eq!(placeholder_keys("{name} in {world} {name}"), vec!["name", "world"]);
+        assert_eq!(placeholder_keys("{{name}} {ping}"), vec!["ping"]);
+    }
+
+    #[test]
+    fn test_expand_with() {
+        let mut values = HashMap::new();
+        values.insert("name".to_string(), "Steve".to_string());
+        values.insert("evil".to_string(), "{name}".to_string());
+        assert_eq!(expand_with("&7{name} {unknown}", &values), "&7Steve {unknown}");
+        // Les valeurs résolues ne sont jamais ré-analysées
+        assert_eq!(expand_with("{evil}", &values), "{name}");
+        assert_eq!(expand_with("{{name}", &values), "{name}");
+    }
+}
@@ -0,0 +1,34 @@
+use crate::config::{PluginConfig, PresetConfig};
+use crate::utils::title_case;
+
+/// Permission nécessaire pour appliquer un preset
+pub fn preset_permission(preset_name: &str) -> String {
+    format!("chat-color:preset.{}", preset_name.to_lowercase())
+}
+
+/// Nom affiché d'un preset : `display_name` de la config, sinon la clé
+pub fn preset_display_name(name: &str, preset: &PresetConfig) -> String {
+    preset.display_name.clone().unwrap_or_else(|| title_case(name))
+}
+
+/// Presets de la config, triés par nom affiché
+pub fn preset_names(config: &PluginConfig) -> Vec<String> {
+    let mut names: Vec<String> = config.presets.keys().cloned().collect();
+    names.sort_by_cached_key(|name| (preset_display_name(name, &config.presets[name]).to_lowercase(), name.clone()));
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_names_sorted_by_display_name() {
+        let config: PluginConfig = serde_yaml::from_str(
+            "save_interval: 300\nauto_save: true\nsimple_colors: {}\ngradients: {}\npresets:\n  royal:\n    name: gold\n    name_format: \"&l\"\n  ocean:\n    display_name: \"Deep Sea\"\n    chat: aqua\nsettings:\n  default_chat_color: white\n  default_name_color: white\n"
+        ).unwrap();
+        assert_eq!(preset_names(&config), vec!["ocean", "royal"]);
+        assert_eq!(preset_display_name("royal", &config.presets["royal"]), "Royal");
+        assert_eq!(preset_permission("Royal"), "chat-color:preset.royal");
+    }
+}
@@ -0,0 +1,174 @@
+use pumpkin_util::text::color::{Color, RGBColor};
+use pumpkin_util::text::TextComponent;
+use crate::config::{ChatColorStyle, GradientMethod, ReadabilityConfig, ReadabilityMode};
+use crate::storage::PLUGIN_CONFIG;
+use crate::utils::{hsv_to_rgb, lerp_rgb, named_color_rgb, walk_component_mut};
+
+/// Luminance relative d'une couleur sRGB (définition WCAG)
+pub fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+/// Rapport de contraste WCAG entre deux couleurs (de 1 à 21)
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (light, dark) = if la > lb { (la, lb) } else { (lb, la) };
+    (light + 0.05) / (dark + 0.05)
+}
+
+/// Éclaircit une couleur (mélange vers le blanc) jusqu'à atteindre le contraste minimum.
+/// Sur un fond clair, la couleur est assombrie (mélange vers le noir) à la place.
+pub fn lighten_to_contrast(rgb: (u8, u8, u8), background: (u8, u8, u8), min_contrast: f64) -> (u8, u8, u8) {
+    if contrast_ratio(rgb, background) >= min_contrast {
+        return rgb;
+    }
+    // Au-delà de cette luminance, le noir contraste plus que le blanc avec le fond
+    let target = if relative_luminance(background) > 0.179 { 0.0 } else { 255.0 };
+    let mix = |c: u8, t: f64| (c as f64 + (target - c as f64) * t).round() as u8;
+    for step in 1..=20 {
+        let t = step as f64 / 20.0;
+        let lighter = (mix(rgb.0, t), mix(rgb.1, t), mix(rgb.2, t));
+        if contrast_ratio(lighter, background) >= min_contrast {
+            return lighter;
+        }
+    }
+    let target = target as u8;
+    (target, target, target)
+}
+
+/// Nombre de couleurs testées le long d'un gradient (ou entre deux couleurs d'un gradient RGB)
+const GRADIENT_SAMPLES: usize = 24;
+
+/// Couleurs d'un gradient HSV échantillonnées de `start_hue` à `end_hue` (en degrés)
+pub fn hue_samples(start_hue: f32, end_hue: f32, saturation: f32, value: f32) -> Vec<(u8, u8, u8)> {
+    (0..=GRADIENT_SAMPLES)
+        .map(|i| {
+            let hue = start_hue + (end_hue - start_hue) * i as f32 / GRADIENT_SAMPLES as f32;
+            hsv_to_rgb(hue / 360.0, saturation, value)
+        })
+        .collect()
+}
+
+/// Couleurs d'un gradient RGB : chaque couleur et des couleurs intermédiaires entre elles
+pub fn stop_samples(stops: &[(u8, u8, u8)]) -> Vec<(u8, u8, u8)> {
+    let mut samples: Vec<(u8, u8, u8)> = stops.windows(2)
+        .flat_map(|pair| (0..GRADIENT_SAMPLES).map(move |i| lerp_rgb(pair[0], pair[1], i as f32 / GRADIENT_SAMPLES as f32)))
+        .collect();
+    samples.extend(stops.last());
+    samples
+}
+
+/// Couleurs que peut prendre un style : la couleur simple, ou des couleurs échantillonnées le long du gradient
+pub async fn style_colors(style: &ChatColorStyle) -> Vec<(u8, u8, u8)> {
+    match style {
+        ChatColorStyle::Simple(color) => vec![named_color_rgb(*color)],
+        ChatColorStyle::Rainbow => hue_samples(0.0, 360.0, 1.0, 1.0),
+        ChatColorStyle::Fire => stop_samples(&[(255, 255, 0), (255, 128, 0), (255, 0, 0)]),
+        ChatColorStyle::CustomGradient(gradient_name) => {
+            let config = PLUGIN_CONFIG.lock().await;
+            let Some(gradient) = config.gradients.get(gradient_name) else {
+                return Vec::new();
+            };
+            match gradient.method {
+                GradientMethod::Hsv => hue_samples(
+                    gradient.start_hue.unwrap_or(0.0),
+                    gradient.end_hue.unwrap_or(360.0),
+                    gradient.saturation.unwrap_or(1.0),
+                    gradient.value.unwrap_or(1.0),
+                ),
+                GradientMethod::RgbInterpolation => {
+                    let stops: Vec<(u8, u8, u8)> = gradient.colors.iter().flatten().map(|c| (c[0], c[1], c[2])).collect();
+                    stop_samples(&stops)
+                }
+            }
+        }
+        ChatColorStyle::PersonalGradient(stops) => {
+            let stops: Vec<(u8, u8, u8)> = stops.iter().map(|c| (c[0], c[1], c[2])).collect();
+            stop_samples(&stops)
+        }
+    }
+}
+
+/// Indique si un style peut être choisi : toujours vrai sauf en mode `reject` quand une de ses couleurs
+/// n'a pas le contraste minimum avec le fond du chat
+pub async fn is_style_readable(style: &ChatColorStyle) -> bool {
+    let readability = PLUGIN_CONFIG.lock().await.readability.clone();
+    if !readability.enabled || readability.mode != ReadabilityMode::Reject {
+        return true;
+    }
+    let background = readability.background_rgb();
+    style_colors(style).await.into_iter()
+        .all(|rgb| contrast_ratio(rgb, background) >= readability.min_contrast)
+}
+
+/// En mode `lighten`, éclaircit dans le composant chaque couleur trop sombre pour le fond du chat
+pub fn lighten_component(component: TextComponent, readability: &ReadabilityConfig) -> TextComponent {
+    if !readability.enabled || readability.mode != ReadabilityMode::Lighten {
+        return component;
+    }
+    let background = readability.background_rgb();
+    let mut base = component.0;
+    walk_component_mut(&mut base, &mut |node| {
+        let rgb = match &node.style.color {
+            Some(Color::Named(color)) => named_color_rgb(*color),
+            Some(Color::Rgb(color)) => (color.red, color.green, color.blue),
+            _ => return,
+        };
+        let lighter = lighten_to_contrast(rgb, background, readability.min_contrast);
+        if lighter != rgb {
+            node.style.color = Some(Color::Rgb(RGBColor::new(lighter.0, lighter.1, lighter.2)));
+        }
+    });
+    TextComponent(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio((0, 0, 0), (0, 0, 0)) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_lighten_to_contrast() {
+        let background = (0, 0, 0);
+        // Le bleu foncé (§1) est illisible sur fond noir et doit être éclairci
+        let lighter = lighten_to_contrast((0, 0, 170), background, 4.5);
+        assert!(contrast_ratio(lighter, background) >= 4.5);
+        // Une couleur déjà lisible n'est pas modifiée
+        assert_eq!(lighten_to_contrast((255, 255, 85), background, 4.5), (255, 255, 85));
+        // Sur fond blanc, le jaune est assombri
+        let white = (255, 255, 255);
+        let darker = lighten_to_contrast((255, 255, 85), white, 4.5);
+        assert!(contrast_ratio(darker, white) >= 4.5);
+    }
+
+    #[test]
+    fn test_hue_samples_cover_full_range() {
+        let rainbow = hue_samples(0.0, 360.0, 1.0, 1.0);
+        // L'arc-en-ciel passe par le bleu pur, illisible sur fond noir au seuil de 3.0
+        assert!(rainbow.contains(&(0, 0, 255)));
+        assert!(rainbow.iter().any(|rgb| contrast_ratio(*rgb, (0, 0, 0)) < 3.0));
+        // 360° revient au rouge au lieu de donner du noir
+        assert_eq!(rainbow.last(), Some(&(255, 0, 0)));
+    }
+
+    #[test]
+    fn test_stop_samples_include_midpoints() {
+        let samples = stop_samples(&[(255, 0, 0), (0, 255, 0)]);
+        assert_eq!(samples.first(), Some(&(255, 0, 0)));
+        assert_eq!(samples.last(), Some(&(0, 255, 0)));
+        assert!(samples.len() > 2);
+    }
+}
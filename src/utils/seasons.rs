@@ -0,0 +1,214 @@
+use pumpkin::server::Server;
+use crate::config::{Availability, ChatColorStyle, PluginConfig};
+use crate::storage::{PLAYER_COLORS, PLAYER_NAME_COLORS, PLUGIN_CONFIG};
+use crate::utils::{apply_name_team, color_to_code, now_secs, save_data, update_tab_name};
+
+/// Date (année, mois, jour) en UTC
+pub type Date = (i64, u32, u32);
+
+/// Borne d'une période : date précise, ou jour de l'année qui revient tous les ans
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowDate {
+    Fixed(i64, u32, u32),
+    Yearly(u32, u32),
+}
+
+/// Convertit un nombre de jours depuis le 1970-01-01 en date (algorithme de H. Hinnant)
+pub fn civil_from_days(days: i64) -> Date {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Date du jour (UTC)
+pub fn today() -> Date {
+    civil_from_days((now_secs() / 86_400) as i64)
+}
+
+/// Nombre de jours d'un mois. Sans année (borne annuelle), février compte 29 jours.
+fn days_in_month(year: Option<i64>, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 => match year {
+            Some(year) if !(year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)) => 28,
+            _ => 29,
+        },
+        _ => 31,
+    }
+}
+
+/// Lit une borne `AAAA-MM-JJ` ou `MM-JJ`
+pub fn parse_window_date(input: &str) -> Option<WindowDate> {
+    let parts: Vec<&str> = input.trim().split('-').collect();
+    let valid = |year: Option<i64>, month: u32, day: u32| {
+        (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month)
+    };
+    match parts.as_slice() {
+        [year, month, day] => {
+            let (year, month, day) = (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+            valid(Some(year), month, day).then_some(WindowDate::Fixed(year, month, day))
+        }
+        [month, day] => {
+            let (month, day) = (month.parse().ok()?, day.parse().ok()?);
+            valid(None, month, day).then_some(WindowDate::Yearly(month, day))
+        }
+        _ => None,
+    }
+}
+
+/// Bornes de période illisibles de la palette (ex: faute de frappe, `02-31`), pour les signaler au chargement
+pub fn invalid_window_dates(config: &PluginConfig) -> Vec<(String, String)> {
+    let colors = config.simple_colors.iter().filter_map(|(name, color)| Some((name, color.availability()?)));
+    let gradients = config.gradients.iter().map(|(name, gradient)| (name, &gradient.availability));
+    let mut invalid = Vec::new();
+    for (name, availability) in colors.chain(gradients) {
+        for date in [&availability.available_from, &availability.available_until].into_iter().flatten() {
+            if parse_window_date(date).is_none() {
+                invalid.push((name.clone(), date.clone()));
+            }
+        }
+    }
+    invalid
+}
+
+/// Indique si une période contient la date donnée. Une borne absente ou invalide ne restreint rien.
+/// Une période annuelle peut passer le nouvel an (ex: du `12-20` au `01-05`).
+pub fn is_available_on(availability: &Availability, date: Date) -> bool {
+    let from = availability.available_from.as_deref().and_then(parse_window_date);
+    let until = availability.available_until.as_deref().and_then(parse_window_date);
+    let (year, month, day) = date;
+
+    if let (Some(WindowDate::Yearly(fm, fd)), Some(WindowDate::Yearly(um, ud))) = (from, until) {
+        let (start, end, now) = ((fm, fd), (um, ud), (month, day));
+        return if start <= end {
+            start <= now && now <= end
+        } else {
+            now >= start || now <= end
+        };
+    }
+
+    let after_start = match from {
+        Some(WindowDate::Fixed(y, m, d)) => (year, month, day) >= (y, m, d),
+        Some(WindowDate::Yearly(m, d)) => (month, day) >= (m, d),
+        None => true,
+    };
+    let before_end = match until {
+        Some(WindowDate::Fixed(y, m, d)) => (year, month, day) <= (y, m, d),
+        Some(WindowDate::Yearly(m, d)) => (month, day) <= (m, d),
+        None => true,
+    };
+    after_start && before_end
+}
+
+/// Indique si une entrée de la palette (couleur simple ou gradient) peut être choisie aujourd'hui
+pub fn is_entry_available(config: &PluginConfig, name: &str) -> bool {
+    let availability = match config.simple_colors.get(name) {
+        Some(color) => color.availability(),
+        None => config.gradients.get(name).map(|gradient| &gradient.availability),
+    };
+    availability.is_none_or(|availability| is_available_on(availability, today()))
+}
+
+/// Indique si un style choisi par un joueur vient d'une entrée saisonnière hors de sa période
+fn is_style_out_of_season(config: &PluginConfig, style: &ChatColorStyle) -> bool {
+    match style {
+        ChatColorStyle::Simple(color) => {
+            // La couleur n'est plus disponible si toutes les entrées qui l'utilisent sont hors période
+            let code = format!("§{}", color_to_code(*color));
+            let mut entries = config.simple_colors.iter().filter(|(_, c)| c.code() == code).peekable();
+            entries.peek().is_some() && entries.all(|(name, _)| !is_entry_available(config, name))
+        }
+        ChatColorStyle::CustomGradient(gradient_name) => {
+            config.gradients.contains_key(gradient_name) && !is_entry_available(config, gradient_name)
+        }
+        _ => false,
+    }
+}
+
+/// Si la config ne garde pas les couleurs saisonnières, retire celles dont la période est terminée
+pub async fn revert_out_of_season_styles(server: &Server) {
+    let removed_names = {
+        let config = PLUGIN_CONFIG.lock().await;
+        if config.settings.keep_seasonal_styles {
+            return;
+        }
+        let mut chat_colors = PLAYER_COLORS.lock().await;
+        let chat_count = chat_colors.len();
+        chat_colors.retain(|_, style| !is_style_out_of_season(&config, style));
+        let mut name_colors = PLAYER_NAME_COLORS.lock().await;
+        let before: Vec<_> = name_colors.keys().copied().collect();
+        name_colors.retain(|_, style| !is_style_out_of_season(&config, style));
+        if chat_colors.len() == chat_count && name_colors.len() == before.len() {
+            return;
+        }
+        before.into_iter().filter(|uuid| !name_colors.contains_key(uuid)).collect::<Vec<_>>()
+    };
+
+    // Nametag et liste des joueurs des joueurs en ligne qui ont perdu leur couleur de pseudo
+    for uuid in &removed_names {
+        if let Some(player) = server.get_player_by_uuid(*uuid).await {
+            apply_name_team(server, &player).await;
+            update_tab_name(server, &player).await;
+        }
+    }
+
+    if let Err(e) = save_data().await {
+        log::error!("[ChatColor] Failed to save player data: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(from: &str, until: &str) -> Availability {
+        Availability {
+            available_from: Some(from.to_string()),
+            available_until: Some(until.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+    }
+
+    #[test]
+    fn test_parse_window_date() {
+        assert_eq!(parse_window_date("12-25"), Some(WindowDate::Yearly(12, 25)));
+        assert_eq!(parse_window_date("02-29"), Some(WindowDate::Yearly(2, 29)));
+        assert_eq!(parse_window_date("2024-02-29"), Some(WindowDate::Fixed(2024, 2, 29)));
+        assert_eq!(parse_window_date("2025-02-29"), None);
+        assert_eq!(parse_window_date("02-31"), None);
+        assert_eq!(parse_window_date("04-31"), None);
+        assert_eq!(parse_window_date("12-1x"), None);
+    }
+
+    #[test]
+    fn test_yearly_window() {
+        let december = window("12-01", "12-31");
+        assert!(is_available_on(&december, (2025, 12, 24)));
+        assert!(!is_available_on(&december, (2025, 11, 30)));
+
+        // Période qui passe le nouvel an
+        let winter = window("12-20", "01-05");
+        assert!(is_available_on(&winter, (2026, 1, 2)));
+        assert!(!is_available_on(&winter, (2026, 2, 1)));
+    }
+
+    #[test]
+    fn test_fixed_window() {
+        let event = window("2025-07-01", "2025-07-14");
+        assert!(is_available_on(&event, (2025, 7, 10)));
+        assert!(!is_available_on(&event, (2026, 7, 10)));
+        assert!(is_available_on(&Availability::default(), (2025, 1, 1)));
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+use pumpkin::entity::player::Player;
+use pumpkin::server::Server;
+use pumpkin_protocol::java::client::play::{CPlayerInfoUpdate, PlayerAction, PlayerInfoFlags};
+use pumpkin_util::text::TextComponent;
+use crate::utils::{render_player_tag, styled_player_name, TagKind};
+
+/// Construit le nom affiché dans la liste des joueurs (tab) : préfixe + pseudo coloré + suffixe
+pub async fn build_tab_name(player: &Player) -> TextComponent {
+    let name = styled_player_name(player).await;
+
+    TextComponent::text("")
+        .add_child(render_player_tag(player, TagKind::Prefix).await)
+        .add_child(name)
+        .add_child(render_player_tag(player, TagKind::Suffix).await)
+}
+
+/// Envoie à tous les joueurs le nom de liste mis à jour du joueur
+pub async fn update_tab_name(server: &Server, player: &Arc<Player>) {
+    let display_name = build_tab_name(player).await;
+    let actions = [PlayerAction::UpdateDisplayName(Some(display_name))];
+    let entries = [pumpkin_protocol::java::client::play::Player {
+        uuid: player.gameprofile.id,
+        actions: &actions,
+    }];
+    let packet = CPlayerInfoUpdate::new(PlayerInfoFlags::UPDATE_DISPLAY_NAME.bits(), &entries);
+    server.broadcast_packet_all(&packet).await;
+}
+
+/// Envoie au joueur qui vient de se connecter les noms de liste des joueurs déjà en ligne
+pub async fn send_tab_names_to(server: &Server, target: &Arc<Player>) {
+    for player in server.get_all_players().await {
+        if player.gameprofile.id == target.gameprofile.id {
+            continue;
+        }
+        let display_name = build_tab_name(&player).await;
+        let actions = [PlayerAction::UpdateDisplayName(Some(display_name))];
+        let entries = [pumpkin_protocol::java::client::play::Player {
+            uuid: player.gameprofile.id,
+            actions: &actions,
+        }];
+        let packet = CPlayerInfoUpdate::new(PlayerInfoFlags::UPDATE_DISPLAY_NAME.bits(), &entries);
+        target.client.enqueue_packet(&packet).await;
+    }
+}
@@ -0,0 +1,107 @@
+use pumpkin::entity::player::Player;
+use pumpkin_util::text::TextComponent;
+use crate::config::TagConfig;
+use crate::storage::{PLAYER_PREFIXES, PLAYER_PRESET_PREFIXES, PLAYER_SUFFIXES, PLUGIN_CONFIG};
+use crate::utils::{apply_style, expand_placeholders, parse_color_codes};
+use crate::commands::chatcolor::get_style_from_config;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TagKind {
+    Prefix,
+    Suffix,
+}
+
+impl TagKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TagKind::Prefix => "prefix",
+            TagKind::Suffix => "suffix",
+        }
+    }
+}
+
+/// Retourne la configuration d'un tag par sa clé
+pub async fn get_tag_config(kind: TagKind, key: &str) -> Option<TagConfig> {
+    let config = PLUGIN_CONFIG.lock().await;
+    let tags = match kind {
+        TagKind::Prefix => &config.prefixes,
+        TagKind::Suffix => &config.suffixes,
+    };
+    tags.get(&key.to_lowercase()).cloned()
+}
+
+/// Détermine le tag actif d'un joueur : celui attribué par un admin en priorité, sinon le tag de plus
+/// haute priorité entre le préfixe de son preset et ceux dont il possède la permission (le preset gagne à égalité).
+pub async fn resolve_tag(player: &Player, kind: TagKind) -> Option<TagConfig> {
+    let assigned = {
+        let map = match kind {
+            TagKind::Prefix => PLAYER_PREFIXES.lock().await,
+            TagKind::Suffix => PLAYER_SUFFIXES.lock().await,
+        };
+        map.get(&player.gameprofile.id).cloned()
+    };
+
+    if let Some(key) = assigned {
+        if let Some(tag) = get_tag_config(kind, &key).await {
+            return Some(tag);
+        }
+        log::warn!("[ChatColor] Unknown {} '{}' assigned to {}", kind.as_str(), key, player.gameprofile.name);
+    }
+
+    // Préfixe posé par un preset : départagé avec les tags de permission par la priorité
+    let preset_tag = match kind {
+        TagKind::Prefix => {
+            let preset_prefix = PLAYER_PRESET_PREFIXES.lock().await.get(&player.gameprofile.id).cloned();
+            match preset_prefix {
+                Some(key) => get_tag_config(kind, &key).await,
+                None => None,
+            }
+        }
+        TagKind::Suffix => None,
+    };
+
+    // Copie des tags pour ne pas garder le verrou pendant les vérifications de permission
+    let mut candidates: Vec<TagConfig> = {
+        let config = PLUGIN_CONFIG.lock().await;
+        let tags = match kind {
+            TagKind::Prefix => &config.prefixes,
+            TagKind::Suffix => &config.suffixes,
+        };
+        tags.values().filter(|tag| tag.permission.is_some()).cloned().collect()
+    };
+    candidates.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+    for tag in candidates {
+        // À priorité égale ou inférieure, le preset choisi par le joueur l'emporte
+        if preset_tag.as_ref().is_some_and(|preset| tag.priority <= preset.priority) {
+            break;
+        }
+        if let Some(node) = &tag.permission {
+            if player.has_permission(node).await {
+                return Some(tag);
+            }
+        }
+    }
+    preset_tag
+}
+
+/// Rend un tag avec sa couleur (simple ou gradient) ou ses propres codes &.
+/// Les placeholders du texte sont remplacés pour le joueur avant le rendu.
+pub async fn render_tag(tag: &TagConfig, player: &Player) -> TextComponent {
+    let text = expand_placeholders(&tag.text, player).await;
+    match &tag.color {
+        Some(color_name) => match get_style_from_config(color_name).await {
+            Some(style) => apply_style(&text, &style).await,
+            None => parse_color_codes(&text),
+        },
+        None => parse_color_codes(&text),
+    }
+}
+
+/// Rend le tag actif d'un joueur, ou un composant vide s'il n'en a pas
+pub async fn render_player_tag(player: &Player, kind: TagKind) -> TextComponent {
+    match resolve_tag(player, kind).await {
+        Some(tag) => render_tag(&tag, player).await,
+        None => TextComponent::text(""),
+    }
+}
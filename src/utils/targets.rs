@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use pumpkin::entity::player::Player;
+use pumpkin::server::Server;
+use uuid::Uuid;
+use crate::storage::{KNOWN_PLAYERS, PLAYER_NAMES};
+
+/// Joueur visé par une commande d'admin, en ligne ou non
+pub struct TargetPlayer {
+    pub uuid: Uuid,
+    pub name: String,
+    pub online: Option<Arc<Player>>,
+}
+
+/// Trouve un joueur par pseudo ou UUID : d'abord parmi les joueurs en ligne,
+/// puis parmi les joueurs déjà venus (data.yml)
+pub async fn resolve_target(server: &Server, input: &str) -> Option<TargetPlayer> {
+    let uuid = Uuid::parse_str(input).ok();
+    for player in server.get_all_players().await {
+        if Some(player.gameprofile.id) == uuid || player.gameprofile.name.eq_ignore_ascii_case(input) {
+            return Some(TargetPlayer {
+                uuid: player.gameprofile.id,
+                name: player.gameprofile.name.clone(),
+                online: Some(player),
+            });
+        }
+    }
+
+    let names = PLAYER_NAMES.lock().await;
+    if let Some(uuid) = uuid {
+        if !KNOWN_PLAYERS.lock().await.contains(&uuid) {
+            return None;
+        }
+        let name = names.get(&uuid).cloned().unwrap_or_else(|| uuid.to_string());
+        return Some(TargetPlayer { uuid, name, online: None });
+    }
+    names.iter()
+        .find(|(_, name)| name.eq_ignore_ascii_case(input))
+        .map(|(uuid, name)| TargetPlayer { uuid: *uuid, name: name.clone(), online: None })
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use pumpkin::entity::player::Player;
+use pumpkin::server::Server;
+use pumpkin::world::scoreboard::Team;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+use uuid::Uuid;
+use crate::config::{ChatColorStyle, GradientMethod};
+use crate::storage::{PLAYER_NAME_COLORS, PLUGIN_CONFIG};
+use crate::utils::{hsv_to_rgb, nearest_named_color};
+
+// Préfixe des équipes gérées par le plugin (évite les collisions avec les autres équipes)
+const TEAM_PREFIX: &str = "cc_";
+
+/// Nom de l'équipe scoreboard associée à un joueur.
+/// Les noms d'équipe restent courts pour rester compatibles avec les anciens clients (16 caractères max).
+pub fn team_name_for(uuid: &Uuid) -> String {
+    let simple = uuid.simple().to_string();
+    format!("{}{}", TEAM_PREFIX, &simple[..13])
+}
+
+/// Couleur dominante d'un style, utilisée pour les nametags.
+/// Les nametags ne supportent pas le RGB lettre par lettre : les gradients sont réduits
+/// à leur première couleur, convertie vers la couleur nommée la plus proche.
+pub async fn dominant_color(style: &ChatColorStyle) -> NamedColor {
+    match style {
+        ChatColorStyle::Simple(color) => *color,
+        // Première lettre du gradient arc-en-ciel : rouge
+        ChatColorStyle::Rainbow => nearest_named_color(hsv_to_rgb(0.0, 1.0, 1.0)),
+        // Première lettre du gradient feu : jaune
+        ChatColorStyle::Fire => nearest_named_color((255, 255, 0)),
+        ChatColorStyle::CustomGradient(gradient_name) => {
+            let config = PLUGIN_CONFIG.lock().await;
+            let Some(gradient) = config.gradients.get(gradient_name) else {
+                return NamedColor::White;
+            };
+            match gradient.method {
+                GradientMethod::Hsv => {
+                    let hue = gradient.start_hue.unwrap_or(0.0) / 360.0;
+                    let rgb = hsv_to_rgb(
+                        hue,
+                        gradient.saturation.unwrap_or(1.0),
+                        gradient.value.unwrap_or(1.0),
+                    );
+                    nearest_named_color(rgb)
+                }
+                GradientMethod::RgbInterpolation => match gradient.colors.as_ref().and_then(|c| c.first()) {
+                    Some(first) => nearest_named_color((first[0], first[1], first[2])),
+                    None => NamedColor::White,
+                },
+            }
+        }
+        ChatColorStyle::PersonalGradient(stops) => match stops.first() {
+            Some(first) => nearest_named_color((first[0], first[1], first[2])),
+            None => NamedColor::White,
+        },
+    }
+}
+
+/// Crée ou met à jour l'équipe du joueur dans tous les mondes pour colorer son nametag.
+/// Si le joueur n'a pas de couleur de pseudo, son équipe est supprimée (nametag vanilla).
+pub async fn apply_name_team(server: &Server, player: &Arc<Player>) {
+    if !PLUGIN_CONFIG.lock().await.settings.nametag_colors {
+        return;
+    }
+
+    let style = {
+        let map = PLAYER_NAME_COLORS.lock().await;
+        map.get(&player.gameprofile.id).cloned()
+    };
+
+    let Some(style) = style else {
+        remove_name_team(server, player).await;
+        return;
+    };
+
+    let color = dominant_color(&style).await;
+    let team_name = team_name_for(&player.gameprofile.id);
+
+    for world in server.worlds.read().await.iter() {
+        let team = Team::new(team_name.clone())
+            .display_name(TextComponent::text(player.gameprofile.name.clone()))
+            .color(color)
+            .members(vec![player.gameprofile.name.clone()]);
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        // Recréer l'équipe permet de mettre à jour la couleur pour tous les clients
+        scoreboard.remove_team(world, &team_name).await;
+        scoreboard.add_team(world, team).await;
+    }
+
+    log::debug!(
+        "[ChatColor] Nametag team {} set to {:?} for {}",
+        team_name, color, player.gameprofile.name
+    );
+}
+
+/// Supprime l'équipe nametag du joueur dans tous les mondes.
+pub async fn remove_name_team(server: &Server, player: &Player) {
+    let team_name = team_name_for(&player.gameprofile.id);
+    for world in server.worlds.read().await.iter() {
+        let mut scoreboard = world.scoreboard.lock().await;
+        scoreboard.remove_team(world, &team_name).await;
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::{PluginConfig, PluginData, PlayerData};
-use crate::storage::{PLAYER_COLORS, PLAYER_NAME_COLORS, PLUGIN_CONFIG};
+use crate::storage::{HIDE_OTHER_COLORS, KNOWN_PLAYERS, PLAYER_ACCESSIBILITY, PLAYER_CHANNELS, PLAYER_COLORS, PLAYER_FOCUSED_CHANNEL, PLAYER_GRANTS, PLAYER_MESSAGE_FORMATS, PLAYER_NAME_COLORS, PLAYER_NAME_FORMATS, PLAYER_NAMES, PLAYER_PREFIXES, PLAYER_PRESET_PREFIXES, PLAYER_SUFFIXES, PLAYER_UNLOCKS, PLUGIN_CONFIG};
+use crate::utils::{invalid_window_dates, PERSONAL_GRADIENT_NAME};
 use std::fs;
 use std::path::Path;
 use std::collections::HashMap;
@@ -34,7 +35,7 @@ fn create_default_config(config_path: &Path) -> Result<(), Box<dyn std::error::E
     ];
     
     for (name, code) in simple_colors {
-        default_simple_colors.insert(name.to_string(), code.to_string());
+        default_simple_colors.insert(name.to_string(), crate::config::SimpleColorConfig::Code(code.to_string()));
     }
     
     // Gradients par défaut
@@ -45,6 +46,8 @@ fn create_default_config(config_path: &Path) -> Result<(), Box<dyn std::error::E
         saturation: Some(1.0),
         value: Some(1.0),
         colors: None,
+        availability: crate::config::Availability::default(),
+        meta: crate::config::EntryMeta::default(),
     });
     
     default_gradients.insert("fire".to_string(), crate::config::GradientConfig {
@@ -54,6 +57,8 @@ fn create_default_config(config_path: &Path) -> Result<(), Box<dyn std::error::E
         saturation: None,
         value: None,
         colors: Some(vec![[255, 0, 0], [255, 165, 0], [255, 255, 0]]),
+        availability: crate::config::Availability::default(),
+        meta: crate::config::EntryMeta::default(),
     });
     
     default_gradients.insert("ocean".to_string(), crate::config::GradientConfig {
@@ -63,6 +68,8 @@ fn create_default_config(config_path: &Path) -> Result<(), Box<dyn std::error::E
         saturation: None,
         value: None,
         colors: Some(vec![[0, 119, 190], [0, 191, 255], [135, 206, 235]]),
+        availability: crate::config::Availability::default(),
+        meta: crate::config::EntryMeta::default(),
     });
     
     default_gradients.insert("sunset".to_string(), crate::config::GradientConfig {
@@ -72,6 +79,8 @@ fn create_default_config(config_path: &Path) -> Result<(), Box<dyn std::error::E
         saturation: None,
         value: None,
         colors: Some(vec![[255, 69, 0], [255, 140, 0], [255, 215, 0], [255, 20, 147]]),
+        availability: crate::config::Availability::default(),
+        meta: crate::config::EntryMeta::default(),
     });
     
     default_gradients.insert("forest".to_string(), crate::config::GradientConfig {
@@ -81,6 +90,41 @@ fn create_default_config(config_path: &Path) -> Result<(), Box<dyn std::error::E
         saturation: None,
         value: None,
         colors: Some(vec![[34, 139, 34], [50, 205, 50], [144, 238, 144]]),
+        availability: crate::config::Availability::default(),
+        meta: crate::config::EntryMeta::default(),
+    });
+    
+    // Préfixe d'exemple
+    let mut default_prefixes = HashMap::new();
+    default_prefixes.insert("vip".to_string(), crate::config::TagConfig {
+        text: "[VIP] ".to_string(),
+        color: Some("gold".to_string()),
+        permission: Some("chat-color:prefix.vip".to_string()),
+        priority: 10,
+    });
+    
+    // Canaux d'exemple
+    let mut default_channels = HashMap::new();
+    default_channels.insert("staff".to_string(), crate::config::ChannelConfig {
+        prefix: "&c[Staff] ".to_string(),
+        color: Some("red".to_string()),
+        permission: Some("chat-color:channel.staff".to_string()),
+    });
+    default_channels.insert("trade".to_string(), crate::config::ChannelConfig {
+        prefix: "&6[Trade] ".to_string(),
+        color: None,
+        permission: None,
+    });
+    
+    // Preset d'exemple : pseudo doré en gras, chat rose
+    let mut default_presets = HashMap::new();
+    default_presets.insert("royal".to_string(), crate::config::PresetConfig {
+        display_name: Some("Royal".to_string()),
+        chat: Some("light_purple".to_string()),
+        name: Some("gold".to_string()),
+        name_format: Some("&l".to_string()),
+        message_format: None,
+        prefix: None,
     });
     
     let default_config = PluginConfig {
@@ -88,9 +132,25 @@ fn create_default_config(config_path: &Path) -> Result<(), Box<dyn std::error::E
         auto_save: true,
         simple_colors: default_simple_colors,
         gradients: default_gradients,
+        local_chat: crate::config::LocalChatConfig::default(),
+        channels: default_channels,
+        private_messages: crate::config::PrivateMessageConfig::default(),
+        emotes: crate::config::EmoteConfig::default(),
+        messages: crate::config::MessagesConfig::default(),
+        readability: crate::config::ReadabilityConfig::default(),
+        prefixes: default_prefixes,
+        suffixes: HashMap::new(),
+        presets: default_presets,
         settings: crate::config::Settings {
             default_chat_color: "white".to_string(),
             default_name_color: "white".to_string(),
+            nametag_colors: true,
+            chat_format: crate::utils::DEFAULT_CHAT_FORMAT.to_string(),
+            chat_scope: crate::config::ChatScope::Server,
+            chat_priority: crate::config::HandlerPriority::Normal,
+            blocked_codes: vec!['k'],
+            max_custom_stops: 5,
+            keep_seasonal_styles: true,
         },
     };
     
@@ -119,7 +179,7 @@ async fn load_existing_config(config_path: &Path) -> Result<(), Box<dyn std::err
     
     log::info!("[ChatColor] YAML content length: {} characters", yaml_content.len());
     
-    let config: PluginConfig = match serde_yaml::from_str(&yaml_content) {
+    let mut config: PluginConfig = match serde_yaml::from_str(&yaml_content) {
         Ok(config) => {
             log::info!("[ChatColor] YAML parsed successfully");
             config
@@ -130,13 +190,25 @@ async fn load_existing_config(config_path: &Path) -> Result<(), Box<dyn std::err
         }
     };
     
+    // Le nom du dégradé personnel est réservé : une entrée de palette du même nom serait confondue avec lui
+    let reserved_color = config.simple_colors.remove(PERSONAL_GRADIENT_NAME).is_some();
+    let reserved_gradient = config.gradients.remove(PERSONAL_GRADIENT_NAME).is_some();
+    if reserved_color || reserved_gradient {
+        log::warn!("[ChatColor] Palette entry '{}' ignored: this name is reserved for personal gradients", PERSONAL_GRADIENT_NAME);
+    }
+    
+    // Une borne de période illisible est ignorée (l'entrée n'est pas restreinte) : on le signale
+    for (name, date) in invalid_window_dates(&config) {
+        log::warn!("[ChatColor] Invalid date '{}' for palette entry '{}', expected YYYY-MM-DD or MM-DD", date, name);
+    }
+    
     // Log pour debug avant le move
-    log::info!("[ChatColor] Config loaded - Simple colors: {}, Gradients: {}", 
-               config.simple_colors.len(), config.gradients.len());
+    log::info!("[ChatColor] Config loaded - Simple colors: {}, Gradients: {}, Prefixes: {}, Suffixes: {}", 
+               config.simple_colors.len(), config.gradients.len(), config.prefixes.len(), config.suffixes.len());
     
     // Log des couleurs simples
-    for (name, code) in &config.simple_colors {
-        log::info!("[ChatColor] Simple color: {} = {}", name, code);
+    for (name, color) in &config.simple_colors {
+        log::info!("[ChatColor] Simple color: {} = {}", name, color.code());
     }
     
     // Log des gradients
@@ -160,30 +232,93 @@ pub async fn save_data() -> Result<(), Box<dyn std::error::Error>> {
     
     let mut plugin_data = PluginData {
         players: HashMap::new(),
+        known_players: KNOWN_PLAYERS.lock().await.iter().map(|uuid| uuid.to_string()).collect(),
+        player_names: PLAYER_NAMES.lock().await.iter().map(|(uuid, name)| (uuid.to_string(), name.clone())).collect(),
     };
+    plugin_data.known_players.sort();
     
     // Récupérer les données des joueurs
     {
         let chat_colors = PLAYER_COLORS.lock().await;
         let name_colors = PLAYER_NAME_COLORS.lock().await;
+        let prefixes = PLAYER_PREFIXES.lock().await;
+        let suffixes = PLAYER_SUFFIXES.lock().await;
+        let channels = PLAYER_CHANNELS.lock().await;
+        let focused_channels = PLAYER_FOCUSED_CHANNEL.lock().await;
+        let accessibility = PLAYER_ACCESSIBILITY.lock().await;
+        let hide_other_colors = HIDE_OTHER_COLORS.lock().await;
+        let grants = PLAYER_GRANTS.lock().await;
+        let unlocks = PLAYER_UNLOCKS.lock().await;
+        let name_formats = PLAYER_NAME_FORMATS.lock().await;
+        let message_formats = PLAYER_MESSAGE_FORMATS.lock().await;
+        let preset_prefixes = PLAYER_PRESET_PREFIXES.lock().await;
         
         for (uuid, chat_color) in chat_colors.iter() {
-            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData {
-                uuid: uuid.to_string(),
-                chat_color: None,
-                name_color: None,
-            });
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
             player_data.chat_color = Some(chat_color.clone());
         }
         
         for (uuid, name_color) in name_colors.iter() {
-            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData {
-                uuid: uuid.to_string(),
-                chat_color: None,
-                name_color: None,
-            });
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
             player_data.name_color = Some(name_color.clone());
         }
+        
+        for (uuid, prefix) in prefixes.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.prefix = Some(prefix.clone());
+        }
+        
+        for (uuid, suffix) in suffixes.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.suffix = Some(suffix.clone());
+        }
+        
+        for (uuid, joined) in channels.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.channels = joined.iter().cloned().collect();
+            player_data.channels.sort();
+        }
+        
+        for (uuid, channel) in focused_channels.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.focused_channel = Some(channel.clone());
+        }
+        
+        for (uuid, mode) in accessibility.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.accessibility = Some(*mode);
+        }
+        
+        for uuid in hide_other_colors.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.hide_other_colors = true;
+        }
+        
+        for (uuid, grant) in grants.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.grant = Some(grant.clone());
+        }
+        
+        for (uuid, unlocked) in unlocks.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.unlocked_colors = unlocked.iter().cloned().collect();
+            player_data.unlocked_colors.sort();
+        }
+        
+        for (uuid, format) in name_formats.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.name_format = Some(format.clone());
+        }
+        
+        for (uuid, format) in message_formats.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.message_format = Some(format.clone());
+        }
+        
+        for (uuid, prefix) in preset_prefixes.iter() {
+            let player_data = plugin_data.players.entry(uuid.to_string()).or_insert_with(|| PlayerData::new(uuid.to_string()));
+            player_data.preset_prefix = Some(prefix.clone());
+        }
     }
     
     // Sauvegarder en YAML
@@ -205,10 +340,24 @@ pub async fn load_data() -> Result<(), Box<dyn std::error::Error>> {
     let yaml_content = fs::read_to_string(data_path)?;
     let plugin_data: PluginData = serde_yaml::from_str(&yaml_content)?;
     
+    // Les joueurs qui ont déjà des données sont aussi connus (data.yml d'avant la liste known_players)
+    let players_with_data: Vec<Uuid> = plugin_data.players.keys().filter_map(|uuid| Uuid::parse_str(uuid).ok()).collect();
+    
     // Charger les données des joueurs
     {
         let mut chat_colors = PLAYER_COLORS.lock().await;
         let mut name_colors = PLAYER_NAME_COLORS.lock().await;
+        let mut prefixes = PLAYER_PREFIXES.lock().await;
+        let mut suffixes = PLAYER_SUFFIXES.lock().await;
+        let mut channels = PLAYER_CHANNELS.lock().await;
+        let mut focused_channels = PLAYER_FOCUSED_CHANNEL.lock().await;
+        let mut accessibility = PLAYER_ACCESSIBILITY.lock().await;
+        let mut hide_other_colors = HIDE_OTHER_COLORS.lock().await;
+        let mut grants = PLAYER_GRANTS.lock().await;
+        let mut unlocks = PLAYER_UNLOCKS.lock().await;
+        let mut name_formats = PLAYER_NAME_FORMATS.lock().await;
+        let mut message_formats = PLAYER_MESSAGE_FORMATS.lock().await;
+        let mut preset_prefixes = PLAYER_PRESET_PREFIXES.lock().await;
         
         for (uuid_str, player_data) in plugin_data.players {
             if let Ok(uuid) = Uuid::parse_str(&uuid_str) {
@@ -218,10 +367,52 @@ pub async fn load_data() -> Result<(), Box<dyn std::error::Error>> {
                 if let Some(name_color) = player_data.name_color {
                     name_colors.insert(uuid, name_color);
                 }
+                if let Some(prefix) = player_data.prefix {
+                    prefixes.insert(uuid, prefix);
+                }
+                if let Some(suffix) = player_data.suffix {
+                    suffixes.insert(uuid, suffix);
+                }
+                if !player_data.channels.is_empty() {
+                    channels.insert(uuid, player_data.channels.into_iter().collect());
+                }
+                if let Some(channel) = player_data.focused_channel {
+                    focused_channels.insert(uuid, channel);
+                }
+                if let Some(mode) = player_data.accessibility {
+                    accessibility.insert(uuid, mode);
+                }
+                if player_data.hide_other_colors {
+                    hide_other_colors.insert(uuid);
+                }
+                if let Some(grant) = player_data.grant {
+                    grants.insert(uuid, grant);
+                }
+                if !player_data.unlocked_colors.is_empty() {
+                    unlocks.insert(uuid, player_data.unlocked_colors.into_iter().collect());
+                }
+                if let Some(format) = player_data.name_format {
+                    name_formats.insert(uuid, format);
+                }
+                if let Some(format) = player_data.message_format {
+                    message_formats.insert(uuid, format);
+                }
+                if let Some(prefix) = player_data.preset_prefix {
+                    preset_prefixes.insert(uuid, prefix);
+                }
             }
         }
     }
     
+    {
+        let mut known_players = KNOWN_PLAYERS.lock().await;
+        known_players.extend(plugin_data.known_players.iter().filter_map(|uuid| Uuid::parse_str(uuid).ok()));
+        known_players.extend(players_with_data);
+    }
+    PLAYER_NAMES.lock().await.extend(
+        plugin_data.player_names.iter().filter_map(|(uuid, name)| Some((Uuid::parse_str(uuid).ok()?, name.clone())))
+    );
+    
     log::info!("[ChatColor] Data loaded successfully");
     Ok(())
 } 
\ No newline at end of file